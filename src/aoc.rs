@@ -0,0 +1,368 @@
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Where the puzzles live, unless told otherwise
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// The environment variable holding the session token. It takes priority over the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!(
+    "github.com/avrahamappel/musical-waffle v",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Find the session token, first in the environment and then in the config file
+pub fn session_token(config: &Path) -> io::Result<String> {
+    token_from(env::var(SESSION_VAR).ok(), config)
+}
+
+fn token_from(var: Option<String>, config: &Path) -> io::Result<String> {
+    let token = match var.filter(|t| !t.trim().is_empty()) {
+        Some(token) => token,
+        None => fs::read_to_string(config).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "No session token in ${SESSION_VAR} or {}: {e}",
+                    config.display()
+                ),
+            )
+        })?,
+    };
+
+    match token.trim() {
+        "" => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is empty", config.display()),
+        )),
+        token => Ok(token.to_string()),
+    }
+}
+
+/// Whether there's a real input at `path`, rather than nothing or an empty placeholder
+pub fn has_input(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// What fetching an input ended up doing
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    /// The input was already on disk, so nothing was requested
+    Cached,
+}
+
+/// What the site made of a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer wasn't checked, because the last one was sent too recently
+    RateLimited {
+        wait_secs: u64,
+    },
+    /// The part was already solved, so there's nothing left to answer
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Read the verdict off the page the site sends back after a submission
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            Some(Self::Right)
+        } else if page.contains("That's not the right answer") {
+            Some(if page.contains("your answer is too high") {
+                Self::TooHigh
+            } else if page.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            })
+        } else if page.contains("You gave an answer too recently") {
+            Some(Self::RateLimited {
+                wait_secs: parse_wait(page).unwrap_or(60),
+            })
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether this says anything about the answer itself
+    pub fn is_checked(self) -> bool {
+        !matches!(self, Self::RateLimited { .. } | Self::AlreadySolved)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Right => write!(f, "right"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::RateLimited { wait_secs } => {
+                write!(
+                    f,
+                    "rate limited, wait {}m {}s",
+                    wait_secs / 60,
+                    wait_secs % 60
+                )
+            }
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Pull the wait out of "You have 1m 30s left to wait"
+fn parse_wait(page: &str) -> Option<u64> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace().try_fold(0, |total, part| {
+        let unit = part.find(|c: char| !c.is_ascii_digit())?;
+        let n: u64 = part[..unit].parse().ok()?;
+        let scale = match &part[unit..] {
+            "h" => 3600,
+            "m" => 60,
+            "s" => 1,
+            _ => return None,
+        };
+        Some(total + n * scale)
+    })
+}
+
+/// Talks to the Advent of Code site, or anything pretending to be it
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    fn url(&self, year: u16, day: u8, page: &str) -> String {
+        format!("{}/{year}/day/{day}{page}", self.base_url)
+    }
+
+    fn get(&self, url: &str) -> io::Result<String> {
+        ureq::get(url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| io::Error::other(format!("Couldn't download {url}: {e}")))
+    }
+
+    /// Send an answer for one part of a day, and find out what the site thought of it
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> io::Result<Verdict> {
+        let url = self.url(year, day, "/answer");
+        let page = ureq::post(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| io::Error::other(format!("Couldn't submit to {url}: {e}")))?;
+
+        Verdict::parse(&page).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Couldn't make sense of the response from {url}"),
+            )
+        })
+    }
+
+    /// Download a day's puzzle input to `path`, unless it's already there. An empty file is a
+    /// placeholder, so it doesn't count. The input is written to a temporary file first, so a
+    /// failed download never leaves a partial input behind.
+    pub fn fetch_input(&self, year: u16, day: u8, path: &Path) -> io::Result<Fetched> {
+        if has_input(path) {
+            return Ok(Fetched::Cached);
+        }
+
+        let input = self.get(&self.url(year, day, "/input"))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, path)?;
+
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+    use crate::utils::temp_path;
+
+    /// A stand-in for the site that answers a single request, and hands back what it was sent
+    fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(l) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = l.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn it_prefers_the_environment_for_the_session_token() {
+        let config = temp_path("session");
+        fs::write(&config, "from-file\n").unwrap();
+
+        assert_eq!(
+            "from-env",
+            token_from(Some("from-env".to_string()), &config).unwrap()
+        );
+        assert_eq!("from-file", token_from(None, &config).unwrap());
+        assert_eq!(
+            "from-file",
+            token_from(Some(" ".to_string()), &config).unwrap()
+        );
+        assert!(token_from(None, &config.with_extension("missing")).is_err());
+
+        fs::remove_file(config).unwrap();
+    }
+
+    #[test]
+    fn it_fetches_an_input_once() {
+        let dir = temp_path("fetch");
+        let path = dir.join("lanternfish.txt");
+        let (url, server) = serve_once("200 OK", "3,4,3,1,2\n");
+        let client = Client::new(&format!("{url}/"), "secret");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "").unwrap();
+
+        assert_eq!(
+            Fetched::Downloaded,
+            client.fetch_input(2021, 6, &path).unwrap()
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2021/day/6/input HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+        assert_eq!("3,4,3,1,2\n", fs::read_to_string(&path).unwrap());
+
+        // The server is gone, so this would fail if it tried to download again
+        assert_eq!(Fetched::Cached, client.fetch_input(2021, 6, &path).unwrap());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn it_leaves_nothing_behind_when_a_fetch_fails() {
+        let dir = temp_path("fetch-failed");
+        let path = dir.join("lanternfish.txt");
+        let (url, server) = serve_once("400 Bad Request", "Please log in");
+
+        assert!(Client::new(&url, "expired")
+            .fetch_input(2021, 6, &path)
+            .is_err());
+        server.join().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn it_parses_verdicts() {
+        let cases = [
+            ("<p>That's the right answer!  You are one gold star closer.</p>", Some(Verdict::Right)),
+            ("<p>That's not the right answer.  If you're stuck...</p>", Some(Verdict::Wrong)),
+            ("<p>That's not the right answer; your answer is too high.</p>", Some(Verdict::TooHigh)),
+            ("<p>That's not the right answer; your answer is too low.</p>", Some(Verdict::TooLow)),
+            (
+                "<p>You gave an answer too recently. You have 1m 5s left to wait.</p>",
+                Some(Verdict::RateLimited { wait_secs: 65 }),
+            ),
+            (
+                "<p>You gave an answer too recently. You have 35s left to wait.</p>",
+                Some(Verdict::RateLimited { wait_secs: 35 }),
+            ),
+            (
+                "<p>You don't seem to be solving the right level.  Did you already complete it?</p>",
+                Some(Verdict::AlreadySolved),
+            ),
+            ("<p>Something else entirely</p>", None),
+        ];
+
+        for (page, verdict) in cases {
+            assert_eq!(verdict, Verdict::parse(page), "{page}");
+        }
+    }
+
+    #[test]
+    fn it_submits_an_answer() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+
+        assert_eq!(
+            Verdict::TooLow,
+            Client::new(&url, "secret")
+                .submit(2021, 6, 2, "123")
+                .unwrap()
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2021/day/6/answer HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+        assert!(request.ends_with("level=2&answer=123"));
+    }
+
+    #[test]
+    fn it_rejects_pages_it_does_not_understand() {
+        let (url, server) = serve_once("200 OK", "<html>Maintenance</html>");
+
+        assert!(Client::new(&url, "secret")
+            .submit(2021, 6, 1, "123")
+            .is_err());
+        server.join().unwrap();
+    }
+}
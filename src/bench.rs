@@ -0,0 +1,313 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::solution::Solution;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts every allocation, so benchmarks can report them. It only
+/// takes effect once a binary installs it with `#[global_allocator]`.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count(bytes: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // Only the growth is new, the rest was counted when it was first allocated
+        Self::count(new_size.saturating_sub(layout.size()));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+fn allocations() -> (u64, u64) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    )
+}
+
+/// Wall time statistics over all iterations, in nanoseconds. With an even number of iterations the
+/// median is the mean of the two middle ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Timing {
+    fn new(samples: &mut [Duration]) -> Self {
+        samples.sort_unstable();
+        let nanos = |d: Duration| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        let total: u128 = samples.iter().map(Duration::as_nanos).sum();
+        let middle = samples.len() / 2;
+        let median = match samples.len() {
+            0 => Duration::ZERO,
+            n if n.is_multiple_of(2) => (samples[middle - 1] + samples[middle]) / 2,
+            _ => samples[middle],
+        };
+
+        Self {
+            min_ns: samples.first().copied().map_or(0, nanos),
+            median_ns: nanos(median),
+            mean_ns: u64::try_from(total / samples.len().max(1) as u128).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Baselines saved before there were other years are all from 2021
+fn default_year() -> u16 {
+    2021
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchResult {
+    #[serde(default = "default_year")]
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub iterations: u32,
+    pub total: Timing,
+    /// Only present for days that parse their input in a separate step
+    pub parse: Option<Timing>,
+    pub solve: Option<Timing>,
+    /// Allocations made by a single run
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+/// Time one part of a day over a number of iterations
+pub fn bench(
+    year: u16,
+    solution: &dyn Solution,
+    part: u8,
+    input: &str,
+    iterations: u32,
+) -> Result<BenchResult> {
+    let iterations = iterations.max(1);
+    let mut totals = Vec::with_capacity(iterations as usize);
+    let mut parses = Vec::with_capacity(iterations as usize);
+    let mut solves = Vec::with_capacity(iterations as usize);
+    let mut allocated = (0, 0);
+
+    for _ in 0..iterations {
+        let (allocations_before, bytes_before) = allocations();
+        let start = Instant::now();
+        match solution.prepare(input) {
+            Some(prepared) => {
+                let prepared = prepared?;
+                let parsed = Instant::now();
+                prepared(part)?;
+
+                parses.push(parsed - start);
+                solves.push(parsed.elapsed());
+            }
+            None => {
+                solution.solve(part, input)?;
+            }
+        }
+        let total = start.elapsed();
+        let (allocations_after, bytes_after) = allocations();
+
+        allocated = (
+            allocations_after - allocations_before,
+            bytes_after - bytes_before,
+        );
+        totals.push(total);
+    }
+
+    let split = |samples: &mut Vec<Duration>| (!samples.is_empty()).then(|| Timing::new(samples));
+
+    Ok(BenchResult {
+        year,
+        day: solution.day(),
+        part,
+        iterations,
+        total: Timing::new(&mut totals),
+        parse: split(&mut parses),
+        solve: split(&mut solves),
+        allocations: allocated.0,
+        allocated_bytes: allocated.1,
+    })
+}
+
+/// A day and part whose median time got worse than the baseline by more than the threshold
+#[derive(Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+/// Compare median times against a baseline. `threshold` is the allowed slowdown, as a fraction.
+pub fn regressions(
+    baseline: &[BenchResult],
+    current: &[BenchResult],
+    threshold: f64,
+) -> Vec<Regression> {
+    current
+        .iter()
+        .filter_map(|c| {
+            let b = baseline
+                .iter()
+                .find(|b| (b.year, b.day, b.part) == (c.year, c.day, c.part))?;
+            #[allow(clippy::cast_precision_loss)]
+            let slower = c.total.median_ns as f64 > b.total.median_ns as f64 * (1.0 + threshold);
+
+            slower.then_some(Regression {
+                year: c.year,
+                day: c.day,
+                part: c.part,
+                baseline_ns: b.total.median_ns,
+                current_ns: c.total.median_ns,
+            })
+        })
+        .collect()
+}
+
+/// Load a saved baseline, or `None` if nothing has been saved there yet
+pub fn load(path: &Path) -> io::Result<Option<Vec<BenchResult>>> {
+    match fs::read_to_string(path) {
+        Ok(baseline) => Ok(Some(serde_json::from_str(&baseline)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Fold new results into a baseline, replacing any for the same year, day and part, so a run over
+/// only some days keeps the rest
+pub fn merge(baseline: &mut Vec<BenchResult>, results: &[BenchResult]) {
+    for result in results {
+        let key = (result.year, result.day, result.part);
+        match baseline.iter_mut().find(|b| (b.year, b.day, b.part) == key) {
+            Some(existing) => *existing = result.clone(),
+            None => baseline.push(result.clone()),
+        }
+    }
+    baseline.sort_by_key(|b| (b.year, b.day, b.part));
+}
+
+pub fn save(path: &Path, results: &[BenchResult]) -> io::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(results)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2021::{binary_diagnostic, sonar_sweep};
+
+    fn result(day: u8, median_ns: u64) -> BenchResult {
+        let timing = Timing {
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+        };
+
+        BenchResult {
+            year: 2021,
+            day,
+            part: 1,
+            iterations: 1,
+            total: timing,
+            parse: None,
+            solve: None,
+            allocations: 0,
+            allocated_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn it_calculates_timing_statistics() {
+        let mut samples = [5, 1, 4, 2].map(Duration::from_nanos);
+
+        assert_eq!(
+            Timing {
+                min_ns: 1,
+                median_ns: 3,
+                mean_ns: 3
+            },
+            Timing::new(&mut samples)
+        );
+        assert_eq!(4, Timing::new(&mut samples[1..]).median_ns);
+    }
+
+    #[test]
+    fn it_flags_regressions_over_the_threshold() {
+        let baseline = [result(1, 100), result(2, 100)];
+        let current = [result(1, 109), result(2, 111), result(3, 500)];
+
+        assert_eq!(
+            vec![Regression {
+                year: 2021,
+                day: 2,
+                part: 1,
+                baseline_ns: 100,
+                current_ns: 111
+            }],
+            regressions(&baseline, &current, 0.1)
+        );
+    }
+
+    #[test]
+    fn it_merges_results_into_the_baseline() {
+        let mut baseline = vec![result(1, 100), result(3, 100), result(8, 100)];
+        merge(&mut baseline, &[result(3, 50), result(2, 70)]);
+
+        assert_eq!(
+            vec![result(1, 100), result(2, 70), result(3, 50), result(8, 100)],
+            baseline
+        );
+    }
+
+    #[test]
+    fn it_reads_baselines_from_before_years() {
+        let old = r#"[{"day": 1, "part": 1, "iterations": 1,
+            "total": {"min_ns": 1, "median_ns": 1, "mean_ns": 1},
+            "parse": null, "solve": null, "allocations": 0, "allocated_bytes": 0}]"#;
+        let results: Vec<BenchResult> = serde_json::from_str(old).unwrap();
+
+        assert_eq!(2021, results[0].year);
+    }
+
+    #[test]
+    fn it_splits_parse_and_solve_times() {
+        let data = "00100\n11110\n10110";
+        let result = bench(2021, &binary_diagnostic::BinaryDiagnostic, 1, data, 3).unwrap();
+
+        assert_eq!(3, result.iterations);
+        assert!(result.parse.is_some());
+        assert!(bench(2021, &sonar_sweep::SonarSweep, 1, "1\n2", 1)
+            .unwrap()
+            .parse
+            .is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong while solving a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input didn't match the puzzle's format. Lines and columns start at 1.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+    },
+    /// A line that should have held something was blank. Lines start at 1.
+    Blank { day: u8, line: usize },
+    /// The input was readable, but no answer could be worked out from it
+    Calc { day: u8, message: String },
+    /// The day doesn't have the requested part
+    NoSuchPart { day: u8, part: u8 },
+    /// The input couldn't be read
+    Io(String),
+}
+
+impl Error {
+    pub(crate) fn parse(day: u8, line: usize, column: usize, text: &str) -> Self {
+        Self::Parse {
+            day,
+            line,
+            column,
+            text: text.to_string(),
+        }
+    }
+
+    pub(crate) fn blank(day: u8, line: usize) -> Self {
+        Self::Blank { day, line }
+    }
+
+    /// Build a parse error for `offending`, which must be a slice of `input`. The line and column
+    /// are worked out from where the slice sits, and only its first line is kept as the text.
+    pub(crate) fn parse_at(day: u8, input: &str, offending: &str) -> Self {
+        let offset = (offending.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit_once('\n')
+            .map_or(before, |(_, l)| l)
+            .chars()
+            .count()
+            + 1;
+        let text = offending.lines().next().unwrap_or_default();
+
+        Self::parse(day, line, column, text)
+    }
+
+    /// Convert a nom error, whose remaining input must be a slice of `input`
+    pub(crate) fn nom(day: u8, input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::parse_at(day, input, e.input),
+            nom::Err::Incomplete(_) => Self::parse_at(day, input, &input[input.len()..]),
+        }
+    }
+
+    /// Move a parse error found in a single line to where that line sits in the whole input
+    pub(crate) fn on_line(self, line: usize) -> Self {
+        match self {
+            Self::Parse {
+                day, column, text, ..
+            } => Self::Parse {
+                day,
+                line,
+                column,
+                text,
+            },
+            other => other,
+        }
+    }
+
+    pub(crate) fn calc(day: u8, message: &str) -> Self {
+        Self::Calc {
+            day,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Parse {
+                day,
+                line,
+                column,
+                text,
+            } if text.is_empty() => write!(
+                f,
+                "Day {day}: unexpected end of input at line {line}, column {column}"
+            ),
+            Self::Parse {
+                day,
+                line,
+                column,
+                text,
+            } => write!(
+                f,
+                "Day {day}: couldn't parse `{text}` at line {line}, column {column}"
+            ),
+            Self::Blank { day, line } => write!(f, "Day {day}: line {line} is blank"),
+            Self::Calc { day, message } => write!(f, "Day {day}: {message}"),
+            Self::NoSuchPart { day, part } => write!(f, "Day {day} has no part {part}"),
+            Self::Io(message) => write!(f, "Couldn't read input: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_line_and_column_of_a_slice() {
+        let input = "1,2\n3,x4\n5";
+        let offending = &input[6..];
+
+        assert_eq!(
+            Error::parse(9, 2, 3, "x4"),
+            Error::parse_at(9, input, offending)
+        );
+    }
+
+    #[test]
+    fn it_points_past_the_end_for_incomplete_input() {
+        let input = "ab\ncd";
+
+        assert_eq!(
+            "Day 1: unexpected end of input at line 2, column 3",
+            Error::parse_at(1, input, &input[input.len()..]).to_string()
+        );
+    }
+
+    #[test]
+    fn it_says_when_a_line_is_blank() {
+        assert_eq!("Day 3: line 2 is blank", Error::blank(3, 2).to_string());
+    }
+}
@@ -0,0 +1,151 @@
+use std::fs;
+use std::io;
+use std::iter::from_fn;
+use std::path::Path;
+
+use crate::solution::Solution;
+use crate::verify::Answers;
+
+/// What a saved puzzle description has to say about its examples
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    /// Every `<pre><code>` block on the page, in order. Not all of them are example inputs, some
+    /// are diagrams.
+    pub blocks: Vec<String>,
+    /// The last emphasized `<code><em>` value in each part's description, which is where the
+    /// answer to the example usually goes
+    pub answers: Vec<String>,
+}
+
+/// Pull the example blocks and answers out of a puzzle description page
+pub fn extract(html: &str) -> Extracted {
+    let blocks = elements(html, "pre")
+        .filter_map(|pre| pre.trim().strip_prefix("<code>")?.strip_suffix("</code>"))
+        .map(text)
+        .collect();
+
+    let mut parts: Vec<_> = elements(html, "article").collect();
+    if parts.is_empty() {
+        parts.push(html);
+    }
+    let answers = parts
+        .into_iter()
+        .filter_map(|part| {
+            elements(part, "code")
+                .filter_map(|code| code.strip_prefix("<em>")?.strip_suffix("</em>"))
+                .last()
+                .map(text)
+        })
+        .collect();
+
+    Extracted { blocks, answers }
+}
+
+/// The contents of every `tag` element. Puzzle pages don't nest the tags this is used for.
+fn elements<'a>(html: &'a str, tag: &str) -> impl Iterator<Item = &'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut rest = html;
+
+    from_fn(move || loop {
+        let after = &rest[rest.find(&open)? + open.len()..];
+        // Skip other tags with the same start, like `<pre>` when looking for `<p>`
+        if !after.starts_with(['>', ' ']) {
+            rest = after;
+            continue;
+        }
+
+        let (_, content) = after.split_once('>')?;
+        let end = content.find(&close)?;
+        rest = &content[end + close.len()..];
+
+        return Some(&content[..end]);
+    })
+}
+
+/// Strip the markup out of some HTML, leaving the text
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].split_once('>').map_or("", |(_, r)| r);
+    }
+    text.push_str(rest);
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Save an example as the fixture for a day in `dir`, and record its answers in `dir/answers.txt`
+pub fn save(
+    dir: &Path,
+    solution: &dyn Solution,
+    example: &str,
+    answers: &[String],
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(format!("{}.txt", solution.name())), example)?;
+
+    let path = dir.join("answers.txt");
+    let mut all = match Answers::load(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Answers::default(),
+        answers => answers?,
+    };
+    for (part, answer) in (1..).zip(answers) {
+        all.insert(solution.day(), part, answer);
+    }
+
+    fs::write(path, all.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: The Treachery of Whales ---</h2>
+<p>For example, consider the following horizontal positions:</p>
+<pre><code>16,1,2,0,4,2,7,1,2,14
+</code></pre>
+<p>This costs a total of <code><em>37</em></code> fuel &amp; is the cheapest.</p>
+</article>
+<p>Your puzzle answer was <code>355989</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>Move from 16 to 5: <em>66</em> fuel &lt;= a lot
+</code></pre>
+<p>This costs a total of <code><em>168</em></code> fuel.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn it_extracts_examples_and_answers() {
+        assert_eq!(
+            Extracted {
+                blocks: vec![
+                    "16,1,2,0,4,2,7,1,2,14\n".to_string(),
+                    "Move from 16 to 5: 66 fuel <= a lot\n".to_string()
+                ],
+                answers: vec!["37".to_string(), "168".to_string()]
+            },
+            extract(PAGE)
+        );
+    }
+
+    #[test]
+    fn it_handles_pages_without_articles() {
+        let page = "<p>Example:</p><pre><code>1\n2</code></pre><p>So <code><em>1</em></code></p>";
+
+        assert_eq!(
+            Extracted {
+                blocks: vec!["1\n2".to_string()],
+                answers: vec!["1".to_string()]
+            },
+            extract(page)
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+
+use crate::error::{Error, Result};
+
+/// The UTF-8 byte order mark some editors put at the start of a file
+pub const BOM: char = '\u{feff}';
+
+/// Clean up an input so parsers don't have to care where it came from. This strips a leading BOM,
+/// turns CRLF line endings into LF, and trims trailing whitespace off every line and off the
+/// input as a whole, which also drops trailing blank lines. Line numbers stay the same, so errors
+/// still point at the right place.
+pub fn normalize(data: &str) -> Cow<'_, str> {
+    let data = data.strip_prefix(BOM).unwrap_or(data).trim_end();
+
+    if data.contains('\r') || data.lines().any(|l| l.ends_with(char::is_whitespace)) {
+        Cow::Owned(
+            data.lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    } else {
+        Cow::Borrowed(data)
+    }
+}
+
+/// Make sure a parser used up the whole (normalized) input, pointing at whatever it left over
+pub(crate) fn finish(day: u8, input: &str, rest: &str) -> Result<()> {
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::parse_at(day, input, rest))
+    }
+}
+
+/// A line with the BOM and trailing whitespace trimmed off, without copying it
+#[derive(Debug)]
+pub struct Line<S> {
+    text: Option<S>,
+    start: usize,
+    end: usize,
+}
+
+impl<S> Line<S> {
+    fn blank() -> Self {
+        Self {
+            text: None,
+            start: 0,
+            end: 0,
+        }
+    }
+}
+
+impl<S: AsRef<str>> AsRef<str> for Line<S> {
+    fn as_ref(&self) -> &str {
+        self.text
+            .as_ref()
+            .map_or("", |t| &t.as_ref()[self.start..self.end])
+    }
+}
+
+/// The line by line version of [`normalize`], for solvers that work through their input as a
+/// stream. Blank lines are held back until a line with content turns up, so trailing ones are
+/// dropped.
+pub struct NormalizedLines<I, S> {
+    lines: I,
+    first: bool,
+    blanks: usize,
+    pending: Option<Line<S>>,
+}
+
+pub fn normalize_lines<I, S>(lines: I) -> NormalizedLines<I, S>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    NormalizedLines {
+        lines,
+        first: true,
+        blanks: 0,
+        pending: None,
+    }
+}
+
+impl<I, S> NormalizedLines<I, S>
+where
+    S: AsRef<str>,
+{
+    fn trim(&mut self, text: S) -> Line<S> {
+        let line = text.as_ref();
+        let start = if std::mem::take(&mut self.first) && line.starts_with(BOM) {
+            BOM.len_utf8()
+        } else {
+            0
+        };
+        let end = start + line[start..].trim_end().len();
+
+        Line {
+            text: Some(text),
+            start,
+            end,
+        }
+    }
+}
+
+impl<I, S> Iterator for NormalizedLines<I, S>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    type Item = Line<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_none() {
+            let text = self.lines.next()?;
+            let line = self.trim(text);
+
+            if line.as_ref().is_empty() {
+                self.blanks += 1;
+            } else {
+                self.pending = Some(line);
+            }
+        }
+
+        if self.blanks > 0 {
+            self.blanks -= 1;
+            return Some(Line::blank());
+        }
+
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_leaves_clean_input_alone() {
+        assert!(matches!(normalize("1,2\n3,4"), Cow::Borrowed("1,2\n3,4")));
+    }
+
+    #[test]
+    fn it_normalizes_input_saved_elsewhere() {
+        assert_eq!(
+            "1,2\n\n3,4",
+            normalize("\u{feff}1,2  \r\n\r\n3,4\r\n\r\n  \n")
+        );
+    }
+
+    #[test]
+    fn it_normalizes_lines() {
+        let lines = ["\u{feff}1 ", "", "2\r", "", "  "];
+
+        assert_eq!(
+            vec!["1", "", "2"],
+            normalize_lines(lines.into_iter())
+                .map(|l| l.as_ref().to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_reports_leftover_input() {
+        let input = "1,2\n3,x";
+        let leftover = "1,2\n\nx";
+
+        assert_eq!(Ok(()), finish(1, input, &input[input.len()..]));
+        assert_eq!(
+            Err(Error::parse(1, 2, 3, "x")),
+            finish(1, input, &input[6..])
+        );
+        assert_eq!(
+            Err(Error::parse(1, 3, 1, "x")),
+            finish(1, leftover, &leftover[3..])
+        );
+    }
+}
@@ -1,10 +1,21 @@
-pub mod binary_diagnostic;
-pub mod dive;
-pub mod giant_squid;
-pub mod hydrothermal_venture;
-pub mod lanternfish;
-pub mod seven_segment_search;
-pub mod sonar_sweep;
-pub mod the_treachery_of_whales;
+pub mod aoc;
+pub mod bench;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
+pub mod submissions;
+pub mod verify;
+pub mod year2021;
 
 mod utils;
+
+pub use error::{Error, Result};
+pub use solution::{Answer, Solution};
+// The 2021 days lived at the top of the crate before there were other years
+pub use year2021::{
+    binary_diagnostic, dive, giant_squid, hydrothermal_venture, lanternfish, seven_segment_search,
+    sonar_sweep, the_treachery_of_whales,
+};
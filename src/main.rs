@@ -1,7 +1,486 @@
 #![allow(clippy::wildcard_imports)]
 
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::{Parser, Subcommand};
+use musical_waffle::aoc::{self, Client, Fetched, Verdict};
+use musical_waffle::bench::{self, BenchResult, CountingAllocator, Timing};
+use musical_waffle::submissions::{Submission, Submissions};
+use musical_waffle::verify::{verify, Answers};
 use musical_waffle::*;
 
-fn main() {
-    //
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one part of a day, reading the puzzle input from a file or stdin
+    Run {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: u8,
+        /// Path to the puzzle input, stdin is used when omitted
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Check every day against its input and expected answer
+    Verify {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        /// Only check this day
+        #[arg(long)]
+        day: Option<u8>,
+        /// Directory holding a directory of puzzle inputs for each year
+        #[arg(long, default_value = "data")]
+        data: PathBuf,
+        /// Expected answers file, defaults to `answers.txt` in the year's data directory
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Time every day and part, and compare with the saved baseline
+    Bench {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        /// Only time this day
+        #[arg(long)]
+        day: Option<u8>,
+        /// Only time this part
+        #[arg(long)]
+        part: Option<u8>,
+        #[arg(long, default_value_t = 10)]
+        iterations: u32,
+        /// Directory holding a directory of puzzle inputs for each year
+        #[arg(long, default_value = "data")]
+        data: PathBuf,
+        /// Saved results to compare against. It's written on the first run, or with `--save`.
+        #[arg(long, default_value = "bench.json")]
+        baseline: PathBuf,
+        /// Update the baseline with this run's results, keeping those for days that weren't run
+        #[arg(long)]
+        save: bool,
+        /// Percentage slowdown of the median time that counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Download a day's puzzle input into the data directory, unless it's already there
+    Fetch {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        /// Directory holding a directory of puzzle inputs for each year
+        #[arg(long, default_value = "data")]
+        data: PathBuf,
+        #[arg(long, default_value = aoc::BASE_URL)]
+        base_url: String,
+        /// File holding the session token, used when `AOC_SESSION` isn't set
+        #[arg(long, default_value = ".aoc-session")]
+        session: PathBuf,
+    },
+    /// Pull the example input and answers out of a saved puzzle page, as a fixture for the tests
+    ExtractExamples {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        /// The saved puzzle description
+        #[arg(long)]
+        html: PathBuf,
+        /// Which `<pre><code>` block on the page holds the example input
+        #[arg(long, default_value_t = 0)]
+        block: usize,
+        /// Directory holding a directory of puzzle inputs for each year. The fixtures go in its
+        /// `examples` directory.
+        #[arg(long, default_value = "data")]
+        data: PathBuf,
+    },
+    /// Start a new day: a module from the template, registered everywhere, with empty inputs
+    NewDay {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        /// Module name, in snake_case
+        #[arg(long)]
+        name: String,
+    },
+    /// Solve one part of a day and send the answer in, unless it's known to be wrong
+    Submit {
+        #[arg(long, default_value_t = 2021)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: u8,
+        /// Directory holding a directory of puzzle inputs for each year
+        #[arg(long, default_value = "data")]
+        data: PathBuf,
+        #[arg(long, default_value = aoc::BASE_URL)]
+        base_url: String,
+        /// File holding the session token, used when `AOC_SESSION` isn't set
+        #[arg(long, default_value = ".aoc-session")]
+        session: PathBuf,
+        /// Every answer sent so far
+        #[arg(long, default_value = "submissions.json")]
+        log: PathBuf,
+    },
+}
+
+/// Where a year's inputs and answers live
+fn year_dir(data: &Path, year: u16) -> PathBuf {
+    data.join(year.to_string())
+}
+
+fn find_day(year: u16, day: u8) -> std::result::Result<&'static dyn Solution, String> {
+    registry::find(year, day).ok_or_else(|| format!("Day {day} of {year} is not solved yet"))
+}
+
+fn select_days(
+    year: u16,
+    day: Option<u8>,
+) -> std::result::Result<Vec<&'static dyn Solution>, String> {
+    let days: Vec<_> = registry::days(year)
+        .ok_or_else(|| format!("There are no solutions for {year}"))?
+        .iter()
+        .copied()
+        .filter(|s| day.is_none_or(|d| s.day() == d))
+        .collect();
+
+    if days.is_empty() {
+        return Err("No matching days".to_string());
+    }
+
+    Ok(days)
+}
+
+fn run(year: u16, day: u8, part: u8, input: Option<PathBuf>) -> std::result::Result<(), String> {
+    let solution = find_day(year, day)?;
+    let answer = match input {
+        Some(path) => {
+            let file =
+                File::open(&path).map_err(|e| format!("Couldn't open {}: {e}", path.display()))?;
+            solution.solve_reader(part, &mut BufReader::new(file))
+        }
+        None => solution.solve_reader(part, &mut io::stdin().lock()),
+    }
+    .map_err(|e| e.to_string())?;
+
+    println!("{answer}");
+    Ok(())
+}
+
+fn verify_days(
+    year: u16,
+    day: Option<u8>,
+    data: &Path,
+    answers: Option<PathBuf>,
+) -> std::result::Result<(), String> {
+    let days = select_days(year, day)?;
+    let data = year_dir(data, year);
+    let answers_path = answers.unwrap_or_else(|| data.join("answers.txt"));
+    let answers = Answers::load(&answers_path)
+        .map_err(|e| format!("Couldn't read {}: {e}", answers_path.display()))?;
+
+    let checks = verify(&days, &data, &answers);
+    for check in &checks {
+        println!("{check}");
+    }
+
+    match checks.iter().filter(|c| c.status.is_failure()).count() {
+        0 => Ok(()),
+        failed => Err(format!("{failed} checks failed")),
+    }
+}
+
+fn format_timing(timing: &Timing) -> String {
+    format!(
+        "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}",
+        Duration::from_nanos(timing.min_ns),
+        Duration::from_nanos(timing.median_ns),
+        Duration::from_nanos(timing.mean_ns)
+    )
+}
+
+fn print_bench_result(result: &BenchResult) {
+    println!("{} day {} part {}", result.year, result.day, result.part);
+    println!("  total  {}", format_timing(&result.total));
+    if let (Some(parse), Some(solve)) = (&result.parse, &result.solve) {
+        println!("  parse  {}", format_timing(parse));
+        println!("  solve  {}", format_timing(solve));
+    }
+    println!(
+        "  allocs {} ({} bytes)",
+        result.allocations, result.allocated_bytes
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn bench_days(
+    year: u16,
+    day: Option<u8>,
+    part: Option<u8>,
+    iterations: u32,
+    data: &Path,
+    baseline_path: &Path,
+    save: bool,
+    threshold: f64,
+) -> std::result::Result<(), String> {
+    let mut results = Vec::new();
+    let data = year_dir(data, year);
+
+    for solution in select_days(year, day)? {
+        let path = data.join(format!("{}.txt", solution.name()));
+        let Ok(input) = fs::read_to_string(&path) else {
+            println!("Day {}: missing input {}", solution.day(), path.display());
+            continue;
+        };
+
+        for p in [1, 2]
+            .into_iter()
+            .filter(|p| part.is_none_or(|part| part == *p))
+        {
+            let result =
+                bench::bench(year, solution, p, &input, iterations).map_err(|e| e.to_string())?;
+            print_bench_result(&result);
+            results.push(result);
+        }
+    }
+
+    let baseline = bench::load(baseline_path)
+        .map_err(|e| format!("Couldn't read {}: {e}", baseline_path.display()))?;
+    if save || baseline.is_none() {
+        let mut merged = baseline.clone().unwrap_or_default();
+        bench::merge(&mut merged, &results);
+        bench::save(baseline_path, &merged)
+            .map_err(|e| format!("Couldn't save {}: {e}", baseline_path.display()))?;
+        println!("Saved results to {}", baseline_path.display());
+    }
+
+    let regressions =
+        bench::regressions(&baseline.unwrap_or_default(), &results, threshold / 100.0);
+    for r in &regressions {
+        println!(
+            "Regression: {} day {} part {} median {:.2?} -> {:.2?}",
+            r.year,
+            r.day,
+            r.part,
+            Duration::from_nanos(r.baseline_ns),
+            Duration::from_nanos(r.current_ns)
+        );
+    }
+
+    match regressions.len() {
+        0 => Ok(()),
+        n => Err(format!(
+            "{n} regressions against {}",
+            baseline_path.display()
+        )),
+    }
+}
+
+fn fetch(
+    year: u16,
+    day: u8,
+    data: &Path,
+    base_url: &str,
+    session: &Path,
+) -> std::result::Result<(), String> {
+    let solution = find_day(year, day)?;
+    let path = year_dir(data, year).join(format!("{}.txt", solution.name()));
+
+    if aoc::has_input(&path) {
+        println!("Already have {}", path.display());
+        return Ok(());
+    }
+
+    let token = aoc::session_token(session).map_err(|e| e.to_string())?;
+    match Client::new(base_url, &token)
+        .fetch_input(year, day, &path)
+        .map_err(|e| e.to_string())?
+    {
+        Fetched::Downloaded => println!("Saved {}", path.display()),
+        Fetched::Cached => println!("Already have {}", path.display()),
+    }
+
+    Ok(())
+}
+
+fn extract_examples(
+    year: u16,
+    day: u8,
+    html: &Path,
+    block: usize,
+    data: &Path,
+) -> std::result::Result<(), String> {
+    let solution = find_day(year, day)?;
+    let examples_dir = year_dir(data, year).join("examples");
+    let page =
+        fs::read_to_string(html).map_err(|e| format!("Couldn't read {}: {e}", html.display()))?;
+    let extracted = examples::extract(&page);
+
+    let Some(example) = extracted.blocks.get(block) else {
+        for (i, b) in extracted.blocks.iter().enumerate() {
+            println!("Block {i}:\n{b}");
+        }
+        return Err(format!(
+            "There's no block {block}, the page has {}",
+            extracted.blocks.len()
+        ));
+    };
+
+    examples::save(&examples_dir, solution, example, &extracted.answers)
+        .map_err(|e| format!("Couldn't save the example: {e}"))?;
+    println!(
+        "Saved the example for day {day} to {} with answers {}",
+        examples_dir.display(),
+        extracted.answers.join(", ")
+    );
+
+    Ok(())
+}
+
+fn new_day(year: u16, day: u8, name: &str) -> std::result::Result<(), String> {
+    if let Some(solution) = registry::find(year, day) {
+        return Err(format!(
+            "Day {day} of {year} is already {}",
+            solution.name()
+        ));
+    }
+
+    let created = scaffold::new_day(Path::new("."), year, day, name)
+        .map_err(|e| format!("Couldn't create day {day}: {e}"))?;
+    for path in created {
+        println!("Created {}", path.display());
+    }
+    println!("Registered {name} as day {day} of {year}");
+
+    Ok(())
+}
+
+fn submit(
+    year: u16,
+    day: u8,
+    part: u8,
+    data: &Path,
+    base_url: &str,
+    session: &Path,
+    log_path: &Path,
+) -> std::result::Result<(), String> {
+    let solution = find_day(year, day)?;
+    let path = year_dir(data, year).join(format!("{}.txt", solution.name()));
+    let input =
+        fs::read_to_string(&path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+    let answer = solution
+        .solve(part, &input)
+        .map_err(|e| e.to_string())?
+        .to_string();
+
+    let mut log = Submissions::load(log_path)
+        .map_err(|e| format!("Couldn't read {}: {e}", log_path.display()))?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    log.check(year, day, part, &answer, now)
+        .map_err(|refusal| format!("Not submitting {answer}: {refusal}"))?;
+
+    let token = aoc::session_token(session).map_err(|e| e.to_string())?;
+    let verdict = Client::new(base_url, &token)
+        .submit(year, day, part, &answer)
+        .map_err(|e| e.to_string())?;
+
+    log.record(Submission {
+        year,
+        day,
+        part,
+        answer: answer.clone(),
+        verdict,
+        at: now,
+    });
+    log.save(log_path)
+        .map_err(|e| format!("Couldn't save {}: {e}", log_path.display()))?;
+
+    match verdict {
+        Verdict::Right => {
+            println!("{answer} is right");
+            Ok(())
+        }
+        verdict => Err(format!("{answer}: {verdict}")),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+        } => run(year, day, part, input),
+        Command::Verify {
+            year,
+            day,
+            data,
+            answers,
+        } => verify_days(year, day, &data, answers),
+        Command::Bench {
+            year,
+            day,
+            part,
+            iterations,
+            data,
+            baseline,
+            save,
+            threshold,
+        } => bench_days(
+            year, day, part, iterations, &data, &baseline, save, threshold,
+        ),
+        Command::Fetch {
+            year,
+            day,
+            data,
+            base_url,
+            session,
+        } => fetch(year, day, &data, &base_url, &session),
+        Command::ExtractExamples {
+            year,
+            day,
+            html,
+            block,
+            data,
+        } => extract_examples(year, day, &html, block, &data),
+        Command::NewDay { year, day, name } => new_day(year, day, &name),
+        Command::Submit {
+            year,
+            day,
+            part,
+            data,
+            base_url,
+            session,
+            log,
+        } => submit(year, day, part, &data, &base_url, &session, &log),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
 }
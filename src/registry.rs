@@ -0,0 +1,39 @@
+use crate::solution::Solution;
+use crate::year2021;
+
+/// Every year with solutions, with its solved days in order
+pub const YEARS: &[(u16, &[&dyn Solution])] = &[(2021, year2021::DAYS)];
+
+/// The solved days of a year, in order
+pub fn days(year: u16) -> Option<&'static [&'static dyn Solution]> {
+    YEARS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map(|(_, days)| *days)
+}
+
+/// Look up a day by its year and number
+pub fn find(year: u16, day: u8) -> Option<&'static dyn Solution> {
+    days(year)?.iter().copied().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_in_order_without_gaps() {
+        for (_, days) in YEARS {
+            for (i, solution) in days.iter().enumerate() {
+                assert_eq!(i + 1, usize::from(solution.day()));
+            }
+        }
+    }
+
+    #[test]
+    fn it_finds_a_day_by_year_and_number() {
+        assert_eq!(Some("Lanternfish"), find(2021, 6).map(|s| s.title()));
+        assert!(find(2021, 25).is_none());
+        assert!(find(2015, 6).is_none());
+    }
+}
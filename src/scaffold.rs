@@ -0,0 +1,298 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// "smoke_basin" -> "Smoke Basin"
+fn title(name: &str) -> String {
+    name.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| w[..1].to_ascii_uppercase() + &w[1..])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Add `pub mod <name>;` to a module list, keeping it sorted
+fn add_mod(lib: &str, name: &str) -> io::Result<String> {
+    let mut lines: Vec<_> = lib.lines().collect();
+    let mods: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, l.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .collect();
+
+    if mods.iter().any(|(_, m)| *m == name) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("`{name}` is already declared"),
+        ));
+    }
+    let (last, _) = mods
+        .last()
+        .ok_or_else(|| invalid("There's no module list to add to".to_string()))?;
+    let at = mods
+        .iter()
+        .find(|(_, m)| *m > name)
+        .map_or(last + 1, |(i, _)| *i);
+
+    let line = format!("pub mod {name};");
+    lines.insert(at, &line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add a day's solution to the end of `DAYS` in its year's module. `DAYS` has every day in order
+/// without gaps, so only the day after its last one can be added.
+fn add_to_days(year: &str, day: u8, name: &str, solution: &str) -> io::Result<String> {
+    let missing = |what: &str| invalid(format!("Couldn't find {what} in the year's module"));
+
+    let days = year
+        .find("pub const DAYS")
+        .ok_or_else(|| missing("`DAYS`"))?;
+    let close = days
+        + year[days..]
+            .find("\n];")
+            .ok_or_else(|| missing("the end of `DAYS`"))?;
+
+    let next = year[days..close]
+        .lines()
+        .filter(|l| l.trim_start().starts_with('&'))
+        .count()
+        + 1;
+    if usize::from(day) != next {
+        return Err(invalid(format!(
+            "Day {day} can't be added yet, the next day to solve is {next}"
+        )));
+    }
+
+    Ok(format!(
+        "{}\n    &{name}::{solution},{}",
+        &year[..close],
+        &year[close..]
+    ))
+}
+
+/// Add commented out placeholders for a day's answers, to be filled in once they're known
+fn add_answer_placeholders(path: &Path, day: u8) -> io::Result<()> {
+    // A hand edited file might have lost its last newline, which would join the placeholders
+    // onto its last answer
+    let unterminated = match fs::read_to_string(path) {
+        Ok(answers) => !answers.is_empty() && !answers.ends_with('\n'),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => false,
+        Err(e) => return Err(e),
+    };
+
+    let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+    if unterminated {
+        writeln!(file)?;
+    }
+    writeln!(file, "# {day} 1 ?\n# {day} 2 ?")
+}
+
+/// Create a module for a new day from the template under `root`, and hook it up everywhere a day
+/// has to be known about: its year's module list and `DAYS`, the answers files and the data
+/// directories. Nothing is touched if the module already exists. Returns the files it created.
+pub fn new_day(root: &Path, year: u16, day: u8, name: &str) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(invalid(format!("There's no day {day}")));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_lowercase())
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(invalid(format!("`{name}` isn't a snake_case module name")));
+    }
+
+    let year_dir = root.join("src").join(format!("year{year}"));
+    let module = year_dir.join(format!("{name}.rs"));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    let title = title(name);
+    let solution = title.replace(' ', "");
+    let year_path = year_dir.join("mod.rs");
+    // Work out every change before writing anything, so a failure leaves the tree alone
+    let year_module = fs::read_to_string(&year_path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "Couldn't read {}, a new year has to be set up by hand: {e}",
+                year_path.display()
+            ),
+        )
+    })?;
+    let year_module = add_to_days(&add_mod(&year_module, name)?, day, name, &solution)?;
+
+    fs::write(
+        &module,
+        TEMPLATE
+            .replace("{{day}}", &day.to_string())
+            .replace("{{name}}", name)
+            .replace("{{title}}", &title)
+            .replace("{{struct}}", &solution),
+    )?;
+    fs::write(year_path, year_module)?;
+
+    let mut created = vec![module];
+    let data = root.join("data").join(year.to_string());
+    for dir in [data.clone(), data.join("examples")] {
+        let placeholder = dir.join(format!("{name}.txt"));
+        if !placeholder.exists() {
+            fs::create_dir_all(&dir)?;
+            fs::write(&placeholder, "")?;
+            created.push(placeholder);
+        }
+        add_answer_placeholders(&dir.join("answers.txt"), day)?;
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_path;
+
+    const YEAR: &str = r"pub mod dive;
+pub mod sonar_sweep;
+
+use crate::solution::Solution;
+
+/// Every solved day of 2021, in order
+pub const DAYS: &[&dyn Solution] = &[
+    &sonar_sweep::SonarSweep,
+    &dive::Dive,
+];
+";
+
+    #[test]
+    fn it_makes_titles() {
+        assert_eq!("Smoke Basin", title("smoke_basin"));
+        assert_eq!("Dive", title("dive"));
+    }
+
+    #[test]
+    fn it_adds_modules_in_order() {
+        let lib = "pub mod dive;\npub mod sonar_sweep;\n\nmod utils;\n";
+
+        assert_eq!(
+            "pub mod dive;\npub mod smoke_basin;\npub mod sonar_sweep;\n\nmod utils;\n",
+            add_mod(lib, "smoke_basin").unwrap()
+        );
+        assert_eq!(
+            "pub mod dive;\npub mod sonar_sweep;\npub mod whales;\n\nmod utils;\n",
+            add_mod(lib, "whales").unwrap()
+        );
+        assert!(add_mod(lib, "dive").is_err());
+    }
+
+    #[test]
+    fn it_registers_the_day() {
+        let year = add_to_days(
+            &add_mod(YEAR, "binary_diagnostic").unwrap(),
+            3,
+            "binary_diagnostic",
+            "BinaryDiagnostic",
+        );
+
+        assert_eq!(
+            Ok("pub mod binary_diagnostic;
+pub mod dive;
+pub mod sonar_sweep;
+
+use crate::solution::Solution;
+
+/// Every solved day of 2021, in order
+pub const DAYS: &[&dyn Solution] = &[
+    &sonar_sweep::SonarSweep,
+    &dive::Dive,
+    &binary_diagnostic::BinaryDiagnostic,
+];
+"),
+            year.as_deref().map_err(|_| ())
+        );
+
+        // Registering any other day would leave `DAYS` out of order or with a gap
+        for day in [2, 4, 12] {
+            let error = add_to_days(YEAR, day, "passage_pathing", "PassagePathing").unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        }
+    }
+
+    #[test]
+    fn it_scaffolds_a_new_day() {
+        let root = temp_path("new-day-scaffold");
+        let data = root.join("data/2021");
+        fs::create_dir_all(root.join("src/year2021")).unwrap();
+        fs::create_dir_all(&data).unwrap();
+        fs::write(root.join("src/year2021/mod.rs"), YEAR).unwrap();
+        // Hand edited, without its last newline
+        fs::write(data.join("answers.txt"), "1 1 7\n1 2 5").unwrap();
+
+        let created = new_day(&root, 2021, 3, "binary_diagnostic").unwrap();
+
+        let module = root.join("src/year2021/binary_diagnostic.rs");
+        assert_eq!(
+            vec![
+                module.clone(),
+                data.join("binary_diagnostic.txt"),
+                data.join("examples/binary_diagnostic.txt"),
+            ],
+            created
+        );
+        let module = fs::read_to_string(module).unwrap();
+        assert!(module.contains("const DAY: u8 = 3;"));
+        assert!(module.contains("pub struct BinaryDiagnostic;"));
+        assert!(module.contains(r#""Binary Diagnostic""#));
+        assert!(module.contains(r#""binary_diagnostic""#));
+        assert!(!module.contains("{{"));
+
+        let year = fs::read_to_string(root.join("src/year2021/mod.rs")).unwrap();
+        assert!(year.starts_with("pub mod binary_diagnostic;\npub mod dive;\n"));
+        assert!(year.contains("    &dive::Dive,\n    &binary_diagnostic::BinaryDiagnostic,\n];"));
+
+        for path in created.iter().skip(1) {
+            assert_eq!("", fs::read_to_string(path).unwrap());
+        }
+        assert_eq!(
+            "1 1 7\n1 2 5\n# 3 1 ?\n# 3 2 ?\n",
+            fs::read_to_string(data.join("answers.txt")).unwrap()
+        );
+        assert_eq!(
+            "# 3 1 ?\n# 3 2 ?\n",
+            fs::read_to_string(data.join("examples/answers.txt")).unwrap()
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn it_refuses_to_overwrite_a_module() {
+        let root = temp_path("new-day");
+        fs::create_dir_all(root.join("src/year2021")).unwrap();
+        fs::write(root.join("src/year2021/dive.rs"), "// Solved").unwrap();
+
+        let error = new_day(&root, 2021, 2, "dive").unwrap_err();
+        assert_eq!(io::ErrorKind::AlreadyExists, error.kind());
+        assert_eq!(
+            "// Solved",
+            fs::read_to_string(root.join("src/year2021/dive.rs")).unwrap()
+        );
+        assert!(new_day(&root, 2021, 26, "giant_squid").is_err());
+        assert!(new_day(&root, 2021, 9, "Smoke-Basin").is_err());
+        // New years have to be set up by hand
+        assert!(new_day(&root, 2022, 1, "calorie_counting").is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
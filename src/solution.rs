@@ -0,0 +1,102 @@
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+
+use crate::error::{Error, Result};
+use crate::utils::read_to_string;
+
+/// The answer to one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(t) => f.write_str(t),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Self::Number(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::Number(n)
+    }
+}
+
+/// Puzzle answers are never negative, so a negative number is kept as text rather than wrapped
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        u64::try_from(n).map_or_else(|_| Self::Text(n.to_string()), Self::Number)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Number(n as u64)
+    }
+}
+
+/// A day's parsed input, ready to solve the given part
+pub type Prepared = Box<dyn FnOnce(u8) -> Result<Answer>>;
+
+/// Hold on to a day's parsed input until it's known which of `part1` or `part2` to solve with it
+pub(crate) fn prepared<T: 'static>(
+    day: u8,
+    parsed: T,
+    part1: fn(T) -> Result<Answer>,
+    part2: fn(T) -> Result<Answer>,
+) -> Prepared {
+    Box::new(move |part| match part {
+        1 => part1(parsed),
+        2 => part2(parsed),
+        _ => Err(Error::NoSuchPart { day, part }),
+    })
+}
+
+/// A common entry point for every day, so tooling doesn't need to know each module's functions
+pub trait Solution: Sync {
+    /// The day of the month the puzzle was released
+    fn day(&self) -> u8;
+
+    /// The puzzle title, as it appears on the website
+    fn title(&self) -> &'static str;
+
+    /// The module name, which is also the name of the input file in `data/<year>/`
+    fn name(&self) -> &'static str;
+
+    fn part1(&self, input: &str) -> Result<Answer>;
+
+    fn part2(&self, input: &str) -> Result<Answer>;
+
+    /// Parse the input ready to solve a part, for days that parse it separately from solving it.
+    /// Benchmarks use this to time the two steps apart.
+    fn prepare(&self, _input: &str) -> Option<Result<Prepared>> {
+        None
+    }
+
+    fn solve(&self, part: u8, input: &str) -> Result<Answer> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(Error::NoSuchPart {
+                day: self.day(),
+                part,
+            }),
+        }
+    }
+
+    /// Solve a part reading the input from `reader`. Days that can work through their input as a
+    /// stream override this, the rest read it all into memory first.
+    fn solve_reader(&self, part: u8, reader: &mut dyn BufRead) -> Result<Answer> {
+        self.solve(part, &read_to_string(reader)?)
+    }
+}
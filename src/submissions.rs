@@ -0,0 +1,206 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::aoc::Verdict;
+
+/// One answer that went out, and what came back
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch
+    pub at: u64,
+}
+
+/// Why an answer wasn't sent
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    Solved {
+        answer: String,
+    },
+    Repeated {
+        verdict: Verdict,
+    },
+    /// At or above an answer that was already too high
+    TooHigh {
+        bound: u64,
+    },
+    /// At or below an answer that was already too low
+    TooLow {
+        bound: u64,
+    },
+    RateLimited {
+        wait_secs: u64,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Solved { answer } => write!(f, "Already solved with {answer}"),
+            Self::Repeated { verdict } => write!(f, "Already submitted, it was {verdict}"),
+            Self::TooHigh { bound } => write!(f, "{bound} was already too high"),
+            Self::TooLow { bound } => write!(f, "{bound} was already too low"),
+            Self::RateLimited { wait_secs } => {
+                write!(f, "Rate limited for another {wait_secs}s")
+            }
+        }
+    }
+}
+
+/// Every answer submitted so far, so nothing is sent that's known to be wrong
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submissions(Vec<Submission>);
+
+impl Submissions {
+    /// Load the log, starting a new one if there isn't one yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(log) => Ok(serde_json::from_str(&log)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.0.push(submission);
+    }
+
+    /// Decide whether an answer is worth sending, given everything sent before it. `now` is in
+    /// seconds since the Unix epoch.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        if let Some(wait_secs) = self
+            .0
+            .iter()
+            .filter_map(|s| match s.verdict {
+                Verdict::RateLimited { wait_secs } => (s.at + wait_secs).checked_sub(now),
+                _ => None,
+            })
+            .max()
+            .filter(|wait| *wait > 0)
+        {
+            return Err(Refusal::RateLimited { wait_secs });
+        }
+
+        let earlier = self
+            .0
+            .iter()
+            .filter(|s| (s.year, s.day, s.part) == (year, day, part) && s.verdict.is_checked());
+        let number = answer.parse::<u64>().ok();
+
+        for s in earlier {
+            match (s.verdict, s.answer.parse::<u64>().ok(), number) {
+                (Verdict::Right, _, _) => {
+                    return Err(Refusal::Solved {
+                        answer: s.answer.clone(),
+                    })
+                }
+                (verdict, _, _) if s.answer == answer => return Err(Refusal::Repeated { verdict }),
+                (Verdict::TooHigh, Some(bound), Some(n)) if n >= bound => {
+                    return Err(Refusal::TooHigh { bound })
+                }
+                (Verdict::TooLow, Some(bound), Some(n)) if n <= bound => {
+                    return Err(Refusal::TooLow { bound })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(part: u8, answer: &str, verdict: Verdict, at: u64) -> Submission {
+        Submission {
+            year: 2021,
+            day: 6,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            at,
+        }
+    }
+
+    #[test]
+    fn it_never_repeats_a_wrong_answer() {
+        let log = Submissions(vec![submission(1, "42", Verdict::Wrong, 0)]);
+
+        assert_eq!(
+            Err(Refusal::Repeated {
+                verdict: Verdict::Wrong
+            }),
+            log.check(2021, 6, 1, "42", 100)
+        );
+        assert_eq!(Ok(()), log.check(2021, 6, 1, "43", 100));
+        assert_eq!(Ok(()), log.check(2021, 6, 2, "42", 100));
+    }
+
+    #[test]
+    fn it_enforces_bounds() {
+        let log = Submissions(vec![
+            submission(1, "100", Verdict::TooHigh, 0),
+            submission(1, "10", Verdict::TooLow, 0),
+        ]);
+
+        assert_eq!(
+            Err(Refusal::TooHigh { bound: 100 }),
+            log.check(2021, 6, 1, "150", 100)
+        );
+        assert_eq!(
+            Err(Refusal::TooLow { bound: 10 }),
+            log.check(2021, 6, 1, "5", 100)
+        );
+        assert_eq!(Ok(()), log.check(2021, 6, 1, "50", 100));
+    }
+
+    #[test]
+    fn it_waits_out_rate_limits() {
+        let log = Submissions(vec![submission(
+            1,
+            "42",
+            Verdict::RateLimited { wait_secs: 60 },
+            1_000,
+        )]);
+
+        assert_eq!(
+            Err(Refusal::RateLimited { wait_secs: 30 }),
+            log.check(2021, 6, 2, "7", 1_030)
+        );
+        // A rate limited answer was never checked, so it can go out again
+        assert_eq!(Ok(()), log.check(2021, 6, 1, "42", 1_060));
+    }
+
+    #[test]
+    fn it_stops_once_a_part_is_solved() {
+        let log = Submissions(vec![submission(2, "7", Verdict::Right, 0)]);
+
+        assert_eq!(
+            Err(Refusal::Solved {
+                answer: "7".to_string()
+            }),
+            log.check(2021, 6, 2, "8", 100)
+        );
+    }
+}
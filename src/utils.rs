@@ -1,19 +1,20 @@
-pub fn div_ceil(a: u32, b: u32) -> u32 {
-    let d = a / b;
-    let r = a % b;
-    if r > 0 && b > 0 {
-        d + 1
-    } else {
-        d
-    }
-}
+use std::io::Read;
+#[cfg(test)]
+use std::path::PathBuf;
+
+use crate::error::Result;
 
-pub trait DivCeil {
-    fn div_ceil(&self, rhs: Self) -> Self;
+/// Read a whole input into memory, for days that can't work through it line by line
+pub fn read_to_string<R: Read>(mut reader: R) -> Result<String> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    Ok(data)
 }
 
-impl DivCeil for u32 {
-    fn div_ceil(&self, rhs: Self) -> Self {
-        div_ceil(*self, rhs)
-    }
+/// A fresh path in the temp dir for a test to write to, cleared of anything an earlier run left
+#[cfg(test)]
+pub(crate) fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("musical-waffle-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    path
 }
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::Error;
+use crate::solution::{Answer, Solution};
+
+/// Expected answers, keyed by day and part
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(HashMap<(u8, u8), String>);
+
+impl Answers {
+    /// Parse an answers file. Each line holds a day, a part and the answer, separated by
+    /// whitespace. Blank lines and lines starting with `#` are ignored.
+    pub fn parse(data: &str) -> io::Result<Self> {
+        data.lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+            .map(|(i, l)| {
+                let mut fields = l.split_whitespace();
+                let key = fields
+                    .next()
+                    .and_then(|d| d.parse().ok())
+                    .zip(fields.next().and_then(|p| p.parse().ok()));
+
+                match (key, fields.next(), fields.next()) {
+                    (Some(key), Some(answer), None) => Ok((key, answer.to_string())),
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid answer on line {}: `{l}`", i + 1),
+                    )),
+                }
+            })
+            .collect::<io::Result<_>>()
+            .map(Self)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: &str) {
+        self.0.insert((day, part), answer.to_string());
+    }
+}
+
+/// Writes the answers back out in the format [`Answers::parse`] reads, sorted by day and part
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "# day part answer")?;
+        let mut answers: Vec<_> = self.0.iter().collect();
+        answers.sort_unstable();
+
+        for ((day, part), answer) in answers {
+            writeln!(f, "{day} {part} {answer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The outcome of checking one part of one day
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass(Answer),
+    Fail {
+        expected: String,
+        actual: Answer,
+    },
+    Error(Error),
+    /// There's no input file for the day, or only an empty placeholder
+    MissingInput,
+    /// The day was solved, but there's no expected answer to compare with
+    MissingAnswer(Answer),
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Fail { .. } | Self::Error(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Pass(answer) => write!(f, "pass ({answer})"),
+            Self::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Self::Error(e) => write!(f, "ERROR ({e})"),
+            Self::MissingInput => write!(f, "missing input"),
+            Self::MissingAnswer(answer) => write!(f, "missing answer (got {answer})"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub title: &'static str,
+    pub status: Status,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {}: {} Part {}: {}",
+            self.day, self.title, self.part, self.status
+        )
+    }
+}
+
+/// Run both parts of every given day against its input in `data_dir`, and compare the results
+/// with the expected answers
+pub fn verify(days: &[&dyn Solution], data_dir: &Path, answers: &Answers) -> Vec<Check> {
+    days.iter()
+        .flat_map(|solution| {
+            let input = fs::read_to_string(data_dir.join(format!("{}.txt", solution.name())))
+                .ok()
+                .filter(|input| !input.is_empty());
+
+            [1, 2].map(|part| {
+                let status = match &input {
+                    None => Status::MissingInput,
+                    Some(input) => match (
+                        solution.solve(part, input),
+                        answers.get(solution.day(), part),
+                    ) {
+                        (Err(e), _) => Status::Error(e),
+                        (Ok(actual), None) => Status::MissingAnswer(actual),
+                        (Ok(actual), Some(expected)) if actual.to_string() == expected => {
+                            Status::Pass(actual)
+                        }
+                        (Ok(actual), Some(expected)) => Status::Fail {
+                            expected: expected.to_string(),
+                            actual,
+                        },
+                    },
+                };
+
+                Check {
+                    day: solution.day(),
+                    part,
+                    title: solution.title(),
+                    status,
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_path;
+
+    #[test]
+    fn it_parses_answers() {
+        let answers = Answers::parse("# day part answer\n1 1 7\n\n1 2 5\n").unwrap();
+
+        assert_eq!(Some("7"), answers.get(1, 1));
+        assert_eq!(Some("5"), answers.get(1, 2));
+        assert_eq!(None, answers.get(2, 1));
+    }
+
+    #[test]
+    fn it_writes_answers_back_out() {
+        let mut answers = Answers::parse("2 1 9\n1 2 5").unwrap();
+        answers.insert(1, 1, "7");
+
+        assert_eq!(
+            "# day part answer\n1 1 7\n1 2 5\n2 1 9\n",
+            answers.to_string()
+        );
+        assert_eq!(answers, Answers::parse(&answers.to_string()).unwrap());
+    }
+
+    #[test]
+    fn it_rejects_malformed_answers() {
+        assert!(Answers::parse("1 1").is_err());
+        assert!(Answers::parse("1 x 3").is_err());
+        assert!(Answers::parse("1 1 3 4").is_err());
+    }
+
+    #[test]
+    fn it_treats_empty_placeholders_as_missing() {
+        let dir = temp_path("verify");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("dive.txt"), "").unwrap();
+
+        let checks = verify(&[&crate::dive::Dive], &dir, &Answers::default());
+        assert_eq!(
+            vec![Status::MissingInput, Status::MissingInput],
+            checks.into_iter().map(|c| c.status).collect::<Vec<_>>()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
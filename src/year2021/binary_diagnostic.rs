@@ -0,0 +1,698 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter, Write};
+use std::io::BufRead;
+
+use itertools::process_results;
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::input::normalize_lines;
+use crate::solution::{prepared, Answer, Prepared, Solution};
+
+const DAY: u8 = 3;
+
+type BitList = Vec<u32>;
+
+/// A set of the report's rows, one bit for each
+type Rows = Vec<u64>;
+
+/// The diagnostic report, with every row packed into words with its first column in the top bit.
+/// Each column is also kept as the set of rows with a 1 in it, so counting a column's bits is a
+/// popcount and narrowing down candidates is a mask.
+struct Report {
+    width: usize,
+    len: usize,
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl Report {
+    fn row_words(&self) -> usize {
+        self.width.div_ceil(64)
+    }
+
+    fn set_words(&self) -> usize {
+        self.len.div_ceil(64)
+    }
+
+    fn bit(&self, row: usize, column: usize) -> u32 {
+        (self.rows[row * self.row_words() + column / 64] >> (63 - column % 64)) as u32 & 1
+    }
+
+    /// A row as it's packed, which sorts in the same order as the numbers it holds
+    fn packed(&self, row: usize) -> &[u64] {
+        let words = self.row_words();
+        &self.rows[row * words..(row + 1) * words]
+    }
+
+    fn row(&self, row: usize) -> BitList {
+        (0..self.width)
+            .map(|column| self.bit(row, column))
+            .collect()
+    }
+
+    /// The rows with a 1 in `column`
+    fn column(&self, column: usize) -> &[u64] {
+        let words = self.set_words();
+        &self.columns[column * words..(column + 1) * words]
+    }
+
+    /// Every row in the report
+    fn all(&self) -> Rows {
+        let mut rows = vec![u64::MAX; self.set_words()];
+        if let Some(last) = rows.last_mut().filter(|_| !self.len.is_multiple_of(64)) {
+            *last = (1 << (self.len % 64)) - 1;
+        }
+
+        rows
+    }
+
+    /// How many of `rows` have a 1 in `column`
+    fn ones(&self, rows: &[u64], column: usize) -> u32 {
+        rows.iter()
+            .zip(self.column(column))
+            .map(|(r, c)| (r & c).count_ones())
+            .sum()
+    }
+}
+
+fn count(rows: &[u64]) -> u32 {
+    rows.iter().map(|r| r.count_ones()).sum()
+}
+
+/// The index of every row in a set
+fn members(rows: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    rows.iter().enumerate().flat_map(|(i, &word)| {
+        (0..64)
+            .filter(move |bit| word >> bit & 1 == 1)
+            .map(move |bit| i * 64 + bit)
+    })
+}
+
+fn parse_report<I, S>(data: I) -> Result<Report>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut width = None;
+    let mut rows = Vec::new();
+    let mut len = 0;
+
+    for (row, s) in normalize_lines(data).enumerate() {
+        let s = s.as_ref();
+        // A blank row has no width to hold the others to
+        if s.is_empty() {
+            return Err(Error::blank(DAY, row + 1));
+        }
+        let width = *width.get_or_insert(s.len());
+        let start = rows.len();
+        rows.resize(start + width.div_ceil(64), 0);
+
+        for (column, c) in s.char_indices() {
+            let bit = c
+                .to_digit(2)
+                .ok_or_else(|| Error::parse(DAY, row + 1, column + 1, &s[column..]))?;
+            if column < width {
+                rows[start + column / 64] |= u64::from(bit) << (63 - column % 64);
+            }
+        }
+        // Point past the end of a short row, or at the first column too many
+        if s.len() != width {
+            let column = width.min(s.len());
+            return Err(Error::parse(DAY, row + 1, column + 1, &s[column..]));
+        }
+        len += 1;
+    }
+    if len == 0 {
+        return Err(Error::calc(DAY, "The diagnostic report is empty"));
+    }
+
+    let mut report = Report {
+        width: width.unwrap_or(0),
+        len,
+        rows,
+        columns: Vec::new(),
+    };
+    let words = report.set_words();
+    report.columns = vec![0; report.width * words];
+    for row in 0..len {
+        for column in 0..report.width {
+            report.columns[column * words + row / 64] |=
+                u64::from(report.bit(row, column)) << (row % 64);
+        }
+    }
+
+    Ok(report)
+}
+
+fn most_common_bits(report: &Report) -> BitList {
+    let rows = report.all();
+
+    (0..report.width)
+        .map(|i| most_common_bit(report, &rows, i))
+        .collect()
+}
+
+fn most_common_bit(report: &Report, rows: &[u64], position: usize) -> u32 {
+    most_common(report.ones(rows, position), count(rows))
+}
+
+/// The more common bit when `ones` out of `total` are 1, which is 1 when they're tied
+fn most_common(digits_sum: u32, total: u32) -> u32 {
+    match digits_sum.cmp(&total.div_ceil(2)) {
+        Ordering::Greater | Ordering::Equal => 1,
+        Ordering::Less => 0,
+    }
+}
+
+fn single_most_common_bitlist(report: &Report) -> BitList {
+    rating(report, most_common_bit, None)
+}
+
+fn least_common_bits(report: &Report) -> BitList {
+    let rows = report.all();
+
+    (0..report.width)
+        .map(|i| least_common_bit(report, &rows, i))
+        .collect()
+}
+
+fn least_common_bit(report: &Report, rows: &[u64], position: usize) -> u32 {
+    least_common(report.ones(rows, position), count(rows))
+}
+
+/// The less common bit when `ones` out of `total` are 1, which is 0 when they're tied
+fn least_common(digits_sum: u32, total: u32) -> u32 {
+    1 - most_common(digits_sum, total)
+}
+
+fn single_least_common_bitlist(report: &Report) -> BitList {
+    rating(report, least_common_bit, None)
+}
+
+/// Narrow the rows down a column at a time to those with the bit `keep` picks, until there's
+/// only one left. A column that every remaining row agrees on is skipped, rather than dropping
+/// them all. Each step is added to `steps`, if it's given.
+fn rating(
+    report: &Report,
+    keep: fn(&Report, &[u64], usize) -> u32,
+    mut steps: Option<&mut Vec<RatingStep>>,
+) -> BitList {
+    let mut rows = report.all();
+
+    for position in 0..report.width {
+        let remaining = count(&rows);
+        if remaining <= 1 {
+            break;
+        }
+
+        let bit = keep(report, &rows, position);
+        let ones = report.ones(&rows, position);
+        let skip = (bit == 1 && ones == 0) || (bit == 0 && ones == remaining);
+        let before = steps.is_some().then(|| rows.clone());
+        if !skip {
+            for (r, c) in rows.iter_mut().zip(report.column(position)) {
+                *r &= if bit == 1 { *c } else { !*c };
+            }
+        }
+
+        if let (Some(steps), Some(before)) = (steps.as_deref_mut(), before) {
+            steps.push(RatingStep {
+                column: position + 1,
+                candidates: remaining,
+                ones,
+                zeros: remaining - ones,
+                kept: if skip { 1 - bit } else { bit },
+                dropped: members(&before)
+                    .filter(|&row| rows[row / 64] >> (row % 64) & 1 == 0)
+                    .map(|row| row + 1)
+                    .collect(),
+            });
+        }
+    }
+
+    rows.iter()
+        .enumerate()
+        .find(|(_, r)| **r != 0)
+        .map(|(i, r)| report.row(i * 64 + r.trailing_zeros() as usize))
+        .unwrap_or_default()
+}
+
+/// How the bits in one column of the whole report add up. Columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnCount {
+    pub column: usize,
+    pub ones: u32,
+    pub zeros: u32,
+    pub most_common: u32,
+    pub least_common: u32,
+}
+
+/// One column's worth of narrowing down the candidates for a rating. Columns and rows start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RatingStep {
+    pub column: usize,
+    pub candidates: u32,
+    pub ones: u32,
+    pub zeros: u32,
+    /// The bit the remaining candidates have. It's the one the rule picks, unless none of the
+    /// candidates have that, in which case they're all kept.
+    pub kept: u32,
+    pub dropped: Vec<usize>,
+}
+
+/// How every rating in a diagnostic report was worked out
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trace {
+    pub columns: Vec<ColumnCount>,
+    pub gamma: u32,
+    pub epsilon: u32,
+    pub power_consumption: u32,
+    pub oxygen_gen: Vec<RatingStep>,
+    pub oxygen_gen_rating: u32,
+    pub co2_scrubber: Vec<RatingStep>,
+    pub co2_scrubber_rating: u32,
+    pub life_support: u32,
+}
+
+impl Trace {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// The trace as text tables
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Column  Ones  Zeros  Most  Least")?;
+        for c in &self.columns {
+            writeln!(
+                f,
+                "{:>6}  {:>4}  {:>5}  {:>4}  {:>5}",
+                c.column, c.ones, c.zeros, c.most_common, c.least_common
+            )?;
+        }
+        writeln!(
+            f,
+            "Gamma {}, epsilon {}, power consumption {}",
+            self.gamma, self.epsilon, self.power_consumption
+        )?;
+
+        for (name, steps, rating) in [
+            ("Oxygen generator", &self.oxygen_gen, self.oxygen_gen_rating),
+            ("CO2 scrubber", &self.co2_scrubber, self.co2_scrubber_rating),
+        ] {
+            writeln!(f, "\n{name} rating")?;
+            writeln!(f, "Column  Candidates  Ones  Zeros  Kept  Dropped rows")?;
+            for step in steps {
+                let mut dropped = String::new();
+                for row in &step.dropped {
+                    write!(
+                        dropped,
+                        "{}{row}",
+                        if dropped.is_empty() { "" } else { ", " }
+                    )?;
+                }
+                writeln!(
+                    f,
+                    "{:>6}  {:>10}  {:>4}  {:>5}  {:>4}  {}",
+                    step.column, step.candidates, step.ones, step.zeros, step.kept, dropped
+                )?;
+            }
+            writeln!(f, "{name} rating {rating}")?;
+        }
+
+        write!(f, "\nLife support {}", self.life_support)
+    }
+}
+
+/// The report's rows in order, so the candidates left while looking for a rating are always a
+/// range of them. Each step splits the range with a binary search rather than rescanning it.
+struct SortedRows<'a> {
+    report: &'a Report,
+    order: Vec<usize>,
+}
+
+impl<'a> SortedRows<'a> {
+    fn new(report: &'a Report) -> Self {
+        let mut order: Vec<_> = (0..report.len).collect();
+        order.sort_unstable_by(|&a, &b| report.packed(a).cmp(report.packed(b)));
+
+        SortedRows { report, order }
+    }
+
+    /// Find a rating the same way as `rating`, with `keep` picking the bit from the number of
+    /// ones and candidates
+    fn rating(&self, keep: fn(u32, u32) -> u32) -> BitList {
+        let (mut start, mut end) = (0, self.order.len());
+
+        for position in 0..self.report.width {
+            if end - start <= 1 {
+                break;
+            }
+
+            // Every candidate shares the bits before `position`, so the zeros come first
+            let split = start
+                + self.order[start..end]
+                    .partition_point(|&row| self.report.bit(row, position) == 0);
+            match keep((end - split) as u32, (end - start) as u32) {
+                1 if split < end => start = split,
+                0 if start < split => end = split,
+                _ => (),
+            }
+        }
+
+        self.report.row(self.order[start])
+    }
+}
+
+fn bin_to_dec(bin: &BitList) -> Result<u32> {
+    bin.iter()
+        .try_fold(0u32, |res, b| res.checked_mul(2).map(|r| r + b))
+        .ok_or_else(too_large)
+}
+
+fn too_large() -> Error {
+    Error::calc(DAY, "The answer doesn't fit in 32 bits")
+}
+
+pub fn diagnose_power_consumption<I, S>(data: I) -> Result<u32>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    power_consumption(&parse_report(data)?)
+}
+
+fn power_consumption(report: &Report) -> Result<u32> {
+    let gamma = most_common_bits(report);
+    let epsilon = least_common_bits(report);
+
+    bin_to_dec(&gamma)?
+        .checked_mul(bin_to_dec(&epsilon)?)
+        .ok_or_else(too_large)
+}
+
+pub fn diagnose_life_support<I, S>(data: I) -> Result<u32>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    life_support(&parse_report(data)?)
+}
+
+fn life_support(report: &Report) -> Result<u32> {
+    let oxygen_gen_rating = single_most_common_bitlist(report);
+    let co2_scrubber_rating = single_least_common_bitlist(report);
+
+    bin_to_dec(&oxygen_gen_rating)?
+        .checked_mul(bin_to_dec(&co2_scrubber_rating)?)
+        .ok_or_else(too_large)
+}
+
+/// Diagnose life support by sorting the report once and narrowing down a range of it for each
+/// rating, instead of filtering every candidate at every bit
+pub fn diagnose_life_support_sorted<I, S>(data: I) -> Result<u32>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    let report = parse_report(data)?;
+    let sorted = SortedRows::new(&report);
+    let oxygen_gen_rating = sorted.rating(most_common);
+    let co2_scrubber_rating = sorted.rating(least_common);
+
+    bin_to_dec(&oxygen_gen_rating)?
+        .checked_mul(bin_to_dec(&co2_scrubber_rating)?)
+        .ok_or_else(too_large)
+}
+
+/// Diagnose the report, keeping track of how each rating was reached
+pub fn explain_diagnostic<I, S>(data: I) -> Result<Trace>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    let report = parse_report(data)?;
+    let all = report.all();
+    let columns: Vec<_> = (0..report.width)
+        .map(|position| {
+            let ones = report.ones(&all, position);
+            ColumnCount {
+                column: position + 1,
+                ones,
+                zeros: count(&all) - ones,
+                most_common: most_common_bit(&report, &all, position),
+                least_common: least_common_bit(&report, &all, position),
+            }
+        })
+        .collect();
+    let gamma = bin_to_dec(&columns.iter().map(|c| c.most_common).collect())?;
+    let epsilon = bin_to_dec(&columns.iter().map(|c| c.least_common).collect())?;
+
+    let (mut oxygen_gen, mut co2_scrubber) = (Vec::new(), Vec::new());
+    let oxygen_gen_rating = bin_to_dec(&rating(&report, most_common_bit, Some(&mut oxygen_gen)))?;
+    let co2_scrubber_rating =
+        bin_to_dec(&rating(&report, least_common_bit, Some(&mut co2_scrubber)))?;
+
+    Ok(Trace {
+        columns,
+        gamma,
+        epsilon,
+        power_consumption: gamma.checked_mul(epsilon).ok_or_else(too_large)?,
+        oxygen_gen,
+        oxygen_gen_rating,
+        co2_scrubber,
+        co2_scrubber_rating,
+        life_support: oxygen_gen_rating
+            .checked_mul(co2_scrubber_rating)
+            .ok_or_else(too_large)?,
+    })
+}
+
+pub fn diagnose_power_consumption_from_reader<R: BufRead>(reader: R) -> Result<u32> {
+    process_results(reader.lines(), |lines| diagnose_power_consumption(lines))?
+}
+
+pub fn diagnose_life_support_from_reader<R: BufRead>(reader: R) -> Result<u32> {
+    process_results(reader.lines(), |lines| diagnose_life_support(lines))?
+}
+
+pub fn explain_diagnostic_from_reader<R: BufRead>(reader: R) -> Result<Trace> {
+    process_results(reader.lines(), |lines| explain_diagnostic(lines))?
+}
+
+pub fn diagnose_life_support_sorted_from_reader<R: BufRead>(reader: R) -> Result<u32> {
+    process_results(reader.lines(), |lines| diagnose_life_support_sorted(lines))?
+}
+
+pub struct BinaryDiagnostic;
+
+impl Solution for BinaryDiagnostic {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Binary Diagnostic"
+    }
+
+    fn name(&self) -> &'static str {
+        "binary_diagnostic"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        diagnose_power_consumption(input.lines()).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        diagnose_life_support(input.lines()).map(Into::into)
+    }
+
+    fn prepare(&self, input: &str) -> Option<Result<Prepared>> {
+        Some(parse_report(input.lines()).map(|report| {
+            prepared(
+                DAY,
+                report,
+                |r| power_consumption(&r).map(Into::into),
+                |r| life_support(&r).map(Into::into),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010"#;
+
+    #[test]
+    fn it_diagnoses_power_consumption() {
+        assert_eq!(Ok(198), diagnose_power_consumption(DATA.lines()));
+    }
+
+    #[test]
+    fn it_diagnoses_life_support() {
+        assert_eq!(Ok(230), diagnose_life_support(DATA.lines()));
+    }
+
+    #[test]
+    fn it_reports_non_binary_digits() {
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 3, "201")),
+            diagnose_power_consumption(["00100", "11201"].into_iter())
+        );
+    }
+
+    #[test]
+    fn it_diagnoses_rows_wider_than_a_word() {
+        let wide = DATA.lines().map(|row| format!("{}{row}", "0".repeat(65)));
+
+        assert_eq!(Ok(230), diagnose_life_support(wide));
+    }
+
+    #[test]
+    fn it_packs_rows_and_columns() {
+        let report = parse_report(DATA.lines()).unwrap();
+
+        assert_eq!((5, 12), (report.width, report.len));
+        assert_eq!(vec![1, 0, 1, 1, 0], report.row(2));
+        assert_eq!(7, report.ones(&report.all(), 0));
+        assert_eq!(vec![1, 0, 1, 1, 1], single_most_common_bitlist(&report));
+        assert_eq!(vec![0, 1, 0, 1, 0], single_least_common_bitlist(&report));
+    }
+
+    #[test]
+    fn it_reports_ragged_rows() {
+        assert_eq!(
+            Err(Error::parse(DAY, 3, 6, "1")),
+            diagnose_power_consumption(["00100", "11110", "101101"].into_iter())
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 4, "")),
+            diagnose_life_support(["00100", "111"].into_iter())
+        );
+        assert_eq!(
+            Err(Error::blank(DAY, 1)),
+            diagnose_power_consumption(["", "101", "001"].into_iter())
+        );
+        assert_eq!(
+            Err(Error::blank(DAY, 2)),
+            diagnose_life_support(["101", "", "001"].into_iter())
+        );
+    }
+
+    #[test]
+    fn it_reports_empty_reports() {
+        let empty = Err(Error::calc(DAY, "The diagnostic report is empty"));
+
+        assert_eq!(empty, diagnose_power_consumption("".lines()));
+        assert_eq!(empty, diagnose_life_support_from_reader("\n\n".as_bytes()));
+    }
+
+    #[test]
+    fn it_reports_answers_too_large_to_give() {
+        let wide = DATA.lines().map(|row| format!("{}{row}", "1".repeat(30)));
+
+        assert_eq!(
+            Err(Error::calc(DAY, "The answer doesn't fit in 32 bits")),
+            diagnose_power_consumption(wide)
+        );
+    }
+
+    #[test]
+    fn it_finds_the_same_ratings_in_sorted_rows() {
+        let input = include_str!("../../data/2021/binary_diagnostic.txt");
+
+        assert_eq!(Ok(230), diagnose_life_support_sorted(DATA.lines()));
+        assert_eq!(
+            diagnose_life_support(input.lines()),
+            diagnose_life_support_sorted_from_reader(input.as_bytes())
+        );
+        // Duplicates, and columns that every candidate agrees on
+        let awkward = ["0110", "0110", "0111", "1110", "0010", "0010"];
+        assert_eq!(
+            diagnose_life_support(awkward.into_iter()),
+            diagnose_life_support_sorted(awkward.into_iter())
+        );
+    }
+
+    #[test]
+    fn it_explains_the_diagnosis() {
+        let trace = explain_diagnostic(DATA.lines()).unwrap();
+
+        assert_eq!(
+            (22, 9, 198, 23, 10, 230),
+            (
+                trace.gamma,
+                trace.epsilon,
+                trace.power_consumption,
+                trace.oxygen_gen_rating,
+                trace.co2_scrubber_rating,
+                trace.life_support
+            )
+        );
+        assert_eq!(
+            RatingStep {
+                column: 1,
+                candidates: 12,
+                ones: 7,
+                zeros: 5,
+                kept: 1,
+                dropped: vec![1, 6, 7, 11, 12]
+            },
+            trace.oxygen_gen[0]
+        );
+        assert_eq!(5, trace.oxygen_gen.len());
+        assert_eq!(3, trace.co2_scrubber.len());
+        assert_eq!(Ok(trace), explain_diagnostic_from_reader(DATA.as_bytes()));
+    }
+
+    #[test]
+    fn it_renders_the_explanation() {
+        let trace = explain_diagnostic(["110", "100", "011"].into_iter()).unwrap();
+
+        assert_eq!(
+            "Column  Ones  Zeros  Most  Least
+     1     2      1     1      0
+     2     2      1     1      0
+     3     1      2     0      1
+Gamma 6, epsilon 1, power consumption 6
+
+Oxygen generator rating
+Column  Candidates  Ones  Zeros  Kept  Dropped rows
+     1           3     2      1     1  3
+     2           2     1      1     1  2
+Oxygen generator rating 6
+
+CO2 scrubber rating
+Column  Candidates  Ones  Zeros  Kept  Dropped rows
+     1           3     2      1     0  1, 2
+CO2 scrubber rating 3
+
+Life support 18",
+            trace.to_string()
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json().unwrap()).unwrap();
+        assert_eq!(18, json["life_support"]);
+        assert_eq!(
+            serde_json::json!([1, 2]),
+            json["co2_scrubber"][0]["dropped"]
+        );
+    }
+}
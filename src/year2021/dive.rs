@@ -0,0 +1,1238 @@
+use std::fmt::{self, Display, Formatter, Write};
+use std::io::BufRead;
+
+use itertools::process_results;
+use nom::character::complete::{alpha1, digit1, space0, space1};
+use nom::combinator::{map_opt, map_res, verify};
+use nom::sequence::{delimited, preceded};
+use nom::IResult;
+
+use crate::error::{Error, Result};
+use crate::input::{self, normalize_lines};
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 2;
+
+/// What to do when an instruction would take the submarine above the surface
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Surfacing {
+    /// Stop plotting with an error
+    #[default]
+    Error,
+    /// Stop at the surface
+    Clamp,
+    /// Carry on with a negative depth
+    Allow,
+}
+
+/// A step on the course worth knowing about. Lines start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The step left the submarine at or above the surface, at `depth` before any clamping
+    Surfaced { line: usize, depth: i64 },
+    /// The step left the aim pointing up
+    AimNegative { line: usize, aim: i64 },
+}
+
+/// Where a submarine is. Submarines that don't aim leave `aim` at 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    pub depth: i64,
+    pub across: i64,
+    /// How far to the right of the starting line
+    pub lateral: i64,
+    pub aim: i64,
+}
+
+impl Position {
+    /// The depth multiplied by the horizontal position, which is what the puzzle asks for
+    pub fn product(&self) -> Result<i64> {
+        self.depth
+            .checked_mul(self.across)
+            .ok_or_else(|| Error::calc(DAY, "The answer doesn't fit in 64 bits"))
+    }
+}
+
+/// `a + b`, or an error naming the line that took the submarine out of range
+fn add(line: usize, a: i64, b: i64) -> Result<i64> {
+    a.checked_add(b).ok_or_else(|| out_of_range(line))
+}
+
+/// `a * b`, or an error naming the line that took the submarine out of range
+fn mul(line: usize, a: i64, b: i64) -> Result<i64> {
+    a.checked_mul(b).ok_or_else(|| out_of_range(line))
+}
+
+fn out_of_range(line: usize) -> Error {
+    Error::calc(
+        DAY,
+        &format!("Line {line} takes the submarine further than 64 bits can track"),
+    )
+}
+
+/// Where a course ended, and the steps on the way that reached the surface or aimed above it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfacingReport {
+    pub position: Position,
+    pub events: Vec<Event>,
+}
+
+/// Keeps a submarine below the surface according to its policy, noting whenever it gets there
+/// if it's been asked to
+struct Surface {
+    surfacing: Surfacing,
+    events: Option<Vec<Event>>,
+}
+
+impl Surface {
+    fn new(surfacing: Surfacing, record: bool) -> Self {
+        Surface {
+            surfacing,
+            events: record.then(Vec::new),
+        }
+    }
+
+    fn note(&mut self, event: Event) {
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+    }
+
+    fn events(&self) -> &[Event] {
+        self.events.as_deref().unwrap_or_default()
+    }
+
+    fn into_events(self) -> Vec<Event> {
+        self.events.unwrap_or_default()
+    }
+
+    /// The depth after changing it by `by` on line `line`
+    fn dive(&mut self, line: usize, depth: i64, by: i64) -> Result<i64> {
+        let depth = add(line, depth, by)?;
+        if by >= 0 || depth > 0 {
+            return Ok(depth);
+        }
+
+        self.note(Event::Surfaced { line, depth });
+        match self.surfacing {
+            Surfacing::Error if depth < 0 => Err(Error::calc(
+                DAY,
+                &format!(
+                    "Line {line} takes the submarine {} above the surface",
+                    depth.unsigned_abs()
+                ),
+            )),
+            Surfacing::Clamp => Ok(0),
+            _ => Ok(depth),
+        }
+    }
+
+    fn aim(&mut self, line: usize, aim: i64) -> i64 {
+        if aim < 0 {
+            self.note(Event::AimNegative { line, aim });
+        }
+
+        aim
+    }
+}
+
+/// A model of how a submarine moves. Implement it to plot courses with different physics.
+pub trait Plotter: Sized {
+    /// Follow every instruction, returning the plotter in its final state
+    fn plot<I, S>(mut self, instructions: I) -> Result<Self>
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for (i, instruction) in instructions.enumerate() {
+            let line = i + 1;
+            self.apply(line, Instruction::parse(line, instruction.as_ref())?)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Move according to the instruction on line number `line`
+    fn apply(&mut self, line: usize, instruction: Instruction) -> Result<()>;
+
+    fn position(&self) -> Position;
+}
+
+/// Moves up and down with `up` and `down`, across with `forward` and `backward`, and sideways
+/// with `left` and `right`. It can't `turn`.
+pub struct Submarine {
+    depth: i64,
+    across: i64,
+    lateral: i64,
+    surface: Surface,
+}
+
+impl Submarine {
+    pub fn new(surfacing: Surfacing) -> Self {
+        Self::with_surface(Surface::new(surfacing, false))
+    }
+
+    /// A submarine that keeps track of its `events`
+    pub fn surveying(surfacing: Surfacing) -> Self {
+        Self::with_surface(Surface::new(surfacing, true))
+    }
+
+    fn with_surface(surface: Surface) -> Self {
+        Submarine {
+            depth: 0,
+            across: 0,
+            lateral: 0,
+            surface,
+        }
+    }
+
+    /// Every step so far that reached the surface or aimed above it, if the submarine is surveying
+    pub fn events(&self) -> &[Event] {
+        self.surface.events()
+    }
+
+    fn report(self) -> SurfacingReport {
+        SurfacingReport {
+            position: self.position(),
+            events: self.surface.into_events(),
+        }
+    }
+}
+
+impl Plotter for Submarine {
+    fn apply(&mut self, line: usize, instruction: Instruction) -> Result<()> {
+        let Instruction(cmd, num) = instruction;
+        let num = i64::from(num);
+
+        match cmd {
+            Command::Up => self.depth = self.surface.dive(line, self.depth, -num)?,
+            Command::Down => self.depth = self.surface.dive(line, self.depth, num)?,
+            Command::Forward => self.across = add(line, self.across, num)?,
+            Command::Backward => self.across = add(line, self.across, -num)?,
+            Command::Right => self.lateral = add(line, self.lateral, num)?,
+            Command::Left => self.lateral = add(line, self.lateral, -num)?,
+            Command::Turn => {
+                return Err(Error::calc(
+                    DAY,
+                    &format!("Line {line} turns, but only an aimed submarine can steer"),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            depth: self.depth,
+            across: self.across,
+            lateral: self.lateral,
+            aim: 0,
+        }
+    }
+}
+
+/// Aims with `up` and `down`, and follows its aim with `forward` and `backward`. `left` and
+/// `right` move sideways without diving, and `turn` changes the heading, in degrees clockwise.
+pub struct AimedSubmarine {
+    depth: i64,
+    across: i64,
+    lateral: i64,
+    aim: i64,
+    heading: u32,
+    surface: Surface,
+}
+
+impl AimedSubmarine {
+    pub fn new(surfacing: Surfacing) -> Self {
+        Self::with_surface(Surface::new(surfacing, false))
+    }
+
+    /// An aimed submarine that keeps track of its `events`
+    pub fn surveying(surfacing: Surfacing) -> Self {
+        Self::with_surface(Surface::new(surfacing, true))
+    }
+
+    fn with_surface(surface: Surface) -> Self {
+        AimedSubmarine {
+            depth: 0,
+            across: 0,
+            lateral: 0,
+            aim: 0,
+            heading: 0,
+            surface,
+        }
+    }
+
+    /// Every step so far that reached the surface or aimed above it, if the submarine is surveying
+    pub fn events(&self) -> &[Event] {
+        self.surface.events()
+    }
+
+    /// Degrees clockwise from the starting heading, which is always a multiple of 90
+    pub fn heading(&self) -> u32 {
+        self.heading
+    }
+
+    fn report(self) -> SurfacingReport {
+        SurfacingReport {
+            position: self.position(),
+            events: self.surface.into_events(),
+        }
+    }
+
+    /// Move `num` ahead, or behind if it's negative, following the aim
+    fn travel(&mut self, line: usize, num: i64) -> Result<()> {
+        let (across, lateral) = self.ahead();
+        self.across = add(line, self.across, across * num)?;
+        self.lateral = add(line, self.lateral, lateral * num)?;
+        self.depth = self
+            .surface
+            .dive(line, self.depth, mul(line, self.aim, num)?)?;
+
+        Ok(())
+    }
+
+    /// Move `num` to the right, or the left if it's negative, without diving
+    fn strafe(&mut self, line: usize, num: i64) -> Result<()> {
+        let (across, lateral) = self.ahead();
+        self.across = add(line, self.across, -lateral * num)?;
+        self.lateral = add(line, self.lateral, across * num)?;
+
+        Ok(())
+    }
+
+    /// How far across and to the right a step ahead goes
+    fn ahead(&self) -> (i64, i64) {
+        match self.heading {
+            0 => (1, 0),
+            90 => (0, 1),
+            180 => (-1, 0),
+            _ => (0, -1),
+        }
+    }
+}
+
+impl Plotter for AimedSubmarine {
+    fn apply(&mut self, line: usize, instruction: Instruction) -> Result<()> {
+        let Instruction(cmd, amount) = instruction;
+        let num = i64::from(amount);
+
+        match cmd {
+            Command::Up => self.aim = self.surface.aim(line, add(line, self.aim, -num)?),
+            Command::Down => self.aim = self.surface.aim(line, add(line, self.aim, num)?),
+            Command::Forward => self.travel(line, num)?,
+            Command::Backward => self.travel(line, -num)?,
+            Command::Right => self.strafe(line, num)?,
+            Command::Left => self.strafe(line, -num)?,
+            Command::Turn => self.heading = (self.heading + amount % 360) % 360,
+        }
+
+        Ok(())
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            depth: self.depth,
+            across: self.across,
+            lateral: self.lateral,
+            aim: self.aim,
+        }
+    }
+}
+
+/// Wraps any plotter to record where it is after every instruction
+pub struct Recording<P> {
+    plotter: P,
+    positions: Vec<Position>,
+}
+
+impl<P: Plotter> Recording<P> {
+    pub fn new(plotter: P) -> Self {
+        let start = plotter.position();
+
+        Recording {
+            plotter,
+            positions: vec![start],
+        }
+    }
+
+    /// The plotter in its final state, and the path it took
+    pub fn finish(self) -> (P, Trajectory) {
+        (
+            self.plotter,
+            Trajectory {
+                positions: self.positions,
+            },
+        )
+    }
+}
+
+impl<P: Plotter> Plotter for Recording<P> {
+    fn apply(&mut self, line: usize, instruction: Instruction) -> Result<()> {
+        self.plotter.apply(line, instruction)?;
+        self.positions.push(self.plotter.position());
+
+        Ok(())
+    }
+
+    fn position(&self) -> Position {
+        self.plotter.position()
+    }
+}
+
+/// Summary statistics for a trajectory. Steps count instructions, with 0 being the start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectoryStats {
+    pub max_depth: i64,
+    /// The first step at which the maximum depth was reached
+    pub max_depth_step: usize,
+    /// The length of the path through all three dimensions
+    pub distance: f64,
+}
+
+/// Where a submarine was at the start and after each instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    positions: Vec<Position>,
+}
+
+impl Trajectory {
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    pub fn stats(&self) -> TrajectoryStats {
+        let (max_depth_step, max_depth) = self.positions.iter().map(|p| p.depth).enumerate().fold(
+            (0, i64::MIN),
+            |max, (i, d)| if d > max.1 { (i, d) } else { max },
+        );
+        let distance = self
+            .positions
+            .windows(2)
+            .map(|w| {
+                let across = (w[1].across - w[0].across) as f64;
+                let lateral = (w[1].lateral - w[0].lateral) as f64;
+                across
+                    .hypot(lateral)
+                    .hypot((w[1].depth - w[0].depth) as f64)
+            })
+            .sum();
+
+        TrajectoryStats {
+            max_depth,
+            max_depth_step,
+            distance,
+        }
+    }
+
+    /// The trajectory as CSV, one row per step
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,depth,across,lateral,aim\n");
+
+        for (i, p) in self.positions.iter().enumerate() {
+            writeln!(csv, "{i},{},{},{},{}", p.depth, p.across, p.lateral, p.aim).unwrap();
+        }
+
+        csv
+    }
+}
+
+const COLOURS: &[&str] = &["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd"];
+
+/// Draw labelled trajectories as an SVG plot of depth against distance across, with depth going
+/// down the page. The axes are scaled separately, as aimed courses go far deeper than they go
+/// across.
+pub fn to_svg(trajectories: &[(&str, &Trajectory)]) -> String {
+    let (width, height, legend) = (800, 400, 24);
+    let positions = || trajectories.iter().flat_map(|(_, t)| &t.positions);
+    let min = |f: fn(&Position) -> i64| positions().map(f).min().unwrap_or(0);
+    let max = |f: fn(&Position) -> i64| positions().map(f).max().unwrap_or(0);
+    let (left, top) = (min(|p| p.across), min(|p| p.depth));
+    let across = (max(|p| p.across) - left).max(1);
+    let down = (max(|p| p.depth) - top).max(1);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{}">"#,
+        height + legend
+    )
+    .unwrap();
+    for (i, (label, _)) in trajectories.iter().enumerate() {
+        writeln!(
+            svg,
+            r#"<text x="{}" y="16" fill="{}">{}</text>"#,
+            4 + i * 160,
+            COLOURS[i % COLOURS.len()],
+            escape(label)
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        r#"<svg y="{legend}" width="{width}" height="{height}" viewBox="{left} {top} {across} {down}" preserveAspectRatio="none">"#
+    )
+    .unwrap();
+    for (i, (label, trajectory)) in trajectories.iter().enumerate() {
+        let points: Vec<_> = trajectory
+            .positions
+            .iter()
+            .map(|p| format!("{},{}", p.across, p.depth))
+            .collect();
+        writeln!(
+            svg,
+            r#"<polyline fill="none" stroke="{}" stroke-width="2" vector-effect="non-scaling-stroke" points="{}"><title>{}</title></polyline>"#,
+            COLOURS[i % COLOURS.len()],
+            points.join(" "),
+            escape(label)
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n</svg>\n");
+
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A command and how far to go with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction(pub Command, pub u32);
+
+impl Instruction {
+    /// Parse the instruction on line number `line`
+    pub fn parse(line: usize, instr: &str) -> Result<Self> {
+        if instr.trim().is_empty() {
+            return Err(Error::blank(DAY, line));
+        }
+        let (rest, instruction) =
+            Self::parser(instr).map_err(|e| Error::nom(DAY, instr, e).on_line(line))?;
+        input::finish(DAY, instr, rest).map_err(|e| e.on_line(line))?;
+
+        Ok(instruction)
+    }
+
+    fn parser(input: &str) -> IResult<&str, Self> {
+        let (input, cmd) = preceded(space0, Command::parse)(input)?;
+        // Headings stay on the grid, so only quarter turns are allowed
+        let (input, num) = delimited(
+            space1,
+            verify(map_res(digit1, str::parse), |n: &u32| {
+                cmd != Command::Turn || n.is_multiple_of(90)
+            }),
+            space0,
+        )(input)?;
+
+        Ok((input, Self(cmd, num)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Up,
+    Down,
+    Forward,
+    Backward,
+    Left,
+    Right,
+    Turn,
+}
+
+impl Command {
+    /// Parse a whole word, so an unknown command is pointed at from its start
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map_opt(alpha1, |word| match word {
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            "forward" => Some(Self::Forward),
+            "backward" => Some(Self::Backward),
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            "turn" => Some(Self::Turn),
+            _ => None,
+        })(input)
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Forward => "forward",
+            Self::Backward => "backward",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Turn => "turn",
+        })
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.0, self.1)
+    }
+}
+
+/// Which of the puzzle's submarines to plan a course for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    Plain,
+    Aimed,
+}
+
+/// `cmd` as many times as it takes to go `num` without overflowing an instruction
+fn repeat(cmd: Command, num: u64) -> impl Iterator<Item = Instruction> {
+    let max = u64::from(u32::MAX);
+
+    (0..num.div_ceil(max)).map(move |i| Instruction(cmd, (num - i * max).min(max) as u32))
+}
+
+/// Plan a short course of `forward`, `up` and `down` that takes a submarine of the given model
+/// from the start to `across` and `depth`, without breaking the surfacing policy. The plain model
+/// needs at most two instructions and the aimed one at most four, unless a distance doesn't fit in
+/// one instruction.
+pub fn synthesize(
+    model: Model,
+    across: i64,
+    depth: i64,
+    surfacing: Surfacing,
+) -> Result<Vec<Instruction>> {
+    let unreachable = |why: &str| {
+        Err(Error::calc(
+            DAY,
+            &format!("Can't reach {across} across at depth {depth}: {why}"),
+        ))
+    };
+
+    if across < 0 {
+        return unreachable("the submarine only goes forward");
+    }
+    if depth < 0 && surfacing != Surfacing::Allow {
+        return unreachable("it's above the surface");
+    }
+
+    let dive = if depth < 0 {
+        Command::Up
+    } else {
+        Command::Down
+    };
+    let (across, depth) = (across.unsigned_abs(), depth.unsigned_abs());
+    let mut course = Vec::new();
+
+    match model {
+        Model::Plain => {
+            course.extend(repeat(dive, depth));
+            course.extend(repeat(Command::Forward, across));
+        }
+        Model::Aimed if across == 0 => {
+            if depth != 0 {
+                return unreachable("the aimed submarine only dives while moving forward");
+            }
+        }
+        // Aim so that most of the way gets the depth down to a multiple of the distance, then aim
+        // one deeper for the rest
+        Model::Aimed => {
+            let (aim, rest) = (depth / across, depth % across);
+            course.extend(repeat(dive, aim));
+            course.extend(repeat(Command::Forward, across - rest));
+            course.extend(repeat(dive, rest.min(1)));
+            course.extend(repeat(Command::Forward, rest));
+        }
+    }
+
+    Ok(course)
+}
+
+pub fn plot_course<I, S>(instructions: I) -> Result<i64>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    plot_with(Submarine::new(Surfacing::Error), instructions).and_then(|s| s.position().product())
+}
+
+pub fn plot_aimed_course<I, S>(instructions: I) -> Result<i64>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    plot_with(AimedSubmarine::new(Surfacing::Error), instructions)
+        .and_then(|s| s.position().product())
+}
+
+/// Plot the course, going above the surface as `surfacing` says, and report where it surfaced
+pub fn survey_course<I, S>(instructions: I, surfacing: Surfacing) -> Result<SurfacingReport>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    plot_with(Submarine::surveying(surfacing), instructions).map(Submarine::report)
+}
+
+/// Plot the aimed course, going above the surface as `surfacing` says, and report where it
+/// surfaced or aimed up
+pub fn survey_aimed_course<I, S>(instructions: I, surfacing: Surfacing) -> Result<SurfacingReport>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    plot_with(AimedSubmarine::surveying(surfacing), instructions).map(AimedSubmarine::report)
+}
+
+/// Plot a course with any model of a submarine, returning it in its final state
+pub fn plot_with<P, I, S>(plotter: P, instructions: I) -> Result<P>
+where
+    P: Plotter,
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    plotter.plot(normalize_lines(instructions))
+}
+
+/// Plot a course with any model of a submarine, recording where it was after every instruction
+pub fn record<P, I, S>(plotter: P, instructions: I) -> Result<(P, Trajectory)>
+where
+    P: Plotter,
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    plot_with(Recording::new(plotter), instructions).map(Recording::finish)
+}
+
+/// Record a course while reading instructions one line at a time
+pub fn record_from_reader<P: Plotter, R: BufRead>(
+    plotter: P,
+    reader: R,
+) -> Result<(P, Trajectory)> {
+    plot_with_from_reader(Recording::new(plotter), reader).map(Recording::finish)
+}
+
+/// Plot a course with any model of a submarine while reading instructions one line at a time
+pub fn plot_with_from_reader<P: Plotter, R: BufRead>(plotter: P, reader: R) -> Result<P> {
+    process_results(reader.lines(), |lines| plot_with(plotter, lines))?
+}
+
+/// Plot the course while reading instructions one line at a time
+pub fn plot_course_from_reader<R: BufRead>(reader: R) -> Result<i64> {
+    process_results(reader.lines(), |lines| plot_course(lines))?
+}
+
+/// Plot the aimed course while reading instructions one line at a time
+pub fn plot_aimed_course_from_reader<R: BufRead>(reader: R) -> Result<i64> {
+    process_results(reader.lines(), |lines| plot_aimed_course(lines))?
+}
+
+/// Survey the course while reading instructions one line at a time
+pub fn survey_course_from_reader<R: BufRead>(
+    reader: R,
+    surfacing: Surfacing,
+) -> Result<SurfacingReport> {
+    process_results(reader.lines(), |lines| survey_course(lines, surfacing))?
+}
+
+/// Survey the aimed course while reading instructions one line at a time
+pub fn survey_aimed_course_from_reader<R: BufRead>(
+    reader: R,
+    surfacing: Surfacing,
+) -> Result<SurfacingReport> {
+    process_results(reader.lines(), |lines| {
+        survey_aimed_course(lines, surfacing)
+    })?
+}
+
+pub struct Dive;
+
+impl Solution for Dive {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Dive!"
+    }
+
+    fn name(&self) -> &'static str {
+        "dive"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        plot_course(input.lines()).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        plot_aimed_course(input.lines()).map(Into::into)
+    }
+
+    fn solve_reader(&self, part: u8, reader: &mut dyn BufRead) -> Result<Answer> {
+        match part {
+            1 => plot_course_from_reader(reader).map(Into::into),
+            2 => plot_aimed_course_from_reader(reader).map(Into::into),
+            _ => Err(Error::NoSuchPart { day: DAY, part }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2"#;
+
+    #[test]
+    fn it_plots_the_course_from_instructions() {
+        assert_eq!(Ok(150), plot_course(DATA.lines()));
+    }
+
+    #[test]
+    fn it_plots_the_aimed_course_from_instructions() {
+        assert_eq!(Ok(900), plot_aimed_course(DATA.lines()));
+    }
+
+    #[test]
+    fn it_plots_courses_from_a_reader() {
+        assert_eq!(Ok(150), plot_course_from_reader(DATA.as_bytes()));
+        assert_eq!(Ok(900), plot_aimed_course_from_reader(DATA.as_bytes()));
+    }
+
+    #[test]
+    fn it_rejects_unknown_commands() {
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 1, "sideways 3")),
+            plot_course(["forward 5", "sideways 3"].into_iter())
+        );
+    }
+
+    #[test]
+    fn it_reads_numbers_of_any_size() {
+        assert_eq!(
+            Ok(15 * 1_000_000),
+            plot_course(["forward 15", "down 1000000"].into_iter())
+        );
+        assert_eq!(
+            Ok(25 * 300),
+            plot_aimed_course(["  down\t12 ", "forward   25", "up 2", "forward 0"].into_iter())
+        );
+    }
+
+    #[test]
+    fn it_reports_courses_too_long_to_track() {
+        assert_eq!(
+            Err(Error::calc(
+                DAY,
+                "Line 2 takes the submarine further than 64 bits can track"
+            )),
+            plot_aimed_course(["down 4294967295", "forward 4294967295"].into_iter())
+        );
+        assert_eq!(
+            Err(Error::calc(DAY, "The answer doesn't fit in 64 bits")),
+            plot_course(
+                [["down 4294967295"; 3], ["forward 4294967295"; 3]]
+                    .concat()
+                    .into_iter()
+            )
+        );
+        assert_eq!(
+            Ok(2 * i64::from(u32::MAX)),
+            plot_course(["down 4294967295", "forward 2"].into_iter())
+        );
+    }
+
+    #[test]
+    fn it_points_at_what_it_cannot_parse() {
+        let cases = [
+            ("forward", Error::parse(DAY, 1, 8, "")),
+            ("forward5", Error::parse(DAY, 1, 8, "5")),
+            ("down 15x", Error::parse(DAY, 1, 8, "x")),
+            ("up -3", Error::parse(DAY, 1, 4, "-3")),
+            ("up 99999999999", Error::parse(DAY, 1, 4, "99999999999")),
+            ("  dive 3", Error::parse(DAY, 1, 3, "dive 3")),
+            ("upward 3", Error::parse(DAY, 1, 1, "upward 3")),
+            ("forwards 3", Error::parse(DAY, 1, 1, "forwards 3")),
+        ];
+
+        for (line, error) in cases {
+            assert_eq!(Err(error), plot_course([line].into_iter()), "{line}");
+        }
+        assert_eq!(
+            Err(Error::blank(DAY, 2)),
+            plot_course(["forward 5", "", "down 5"].into_iter())
+        );
+    }
+
+    const SURFACING: &str = r#"down 3
+forward 2
+up 5
+down 1
+up 1
+forward 4"#;
+
+    #[test]
+    fn it_refuses_to_fly_by_default() {
+        assert_eq!(
+            Err(Error::calc(
+                DAY,
+                "Line 3 takes the submarine 2 above the surface"
+            )),
+            plot_course(SURFACING.lines())
+        );
+        assert_eq!(
+            Err(Error::calc(
+                DAY,
+                "Line 3 takes the submarine 2 above the surface"
+            )),
+            survey_course_from_reader(SURFACING.as_bytes(), Surfacing::Error)
+        );
+    }
+
+    #[test]
+    fn it_clamps_to_the_surface() {
+        assert_eq!(
+            Ok(SurfacingReport {
+                position: Position {
+                    depth: 0,
+                    across: 6,
+                    lateral: 0,
+                    aim: 0,
+                },
+                events: vec![
+                    Event::Surfaced { line: 3, depth: -2 },
+                    Event::Surfaced { line: 5, depth: 0 },
+                ]
+            }),
+            survey_course(SURFACING.lines(), Surfacing::Clamp)
+        );
+    }
+
+    #[test]
+    fn it_allows_negative_depths() {
+        assert_eq!(
+            Ok(SurfacingReport {
+                position: Position {
+                    depth: -2,
+                    across: 6,
+                    lateral: 0,
+                    aim: 0,
+                },
+                events: vec![
+                    Event::Surfaced { line: 3, depth: -2 },
+                    Event::Surfaced { line: 5, depth: -2 },
+                ]
+            }),
+            survey_course(SURFACING.lines(), Surfacing::Allow)
+        );
+    }
+
+    #[test]
+    fn it_reports_when_the_aim_goes_up() {
+        assert_eq!(
+            Ok(SurfacingReport {
+                position: Position {
+                    depth: -2,
+                    across: 6,
+                    lateral: 0,
+                    aim: -2,
+                },
+                events: vec![
+                    Event::AimNegative { line: 3, aim: -2 },
+                    Event::AimNegative { line: 4, aim: -1 },
+                    Event::AimNegative { line: 5, aim: -2 },
+                    Event::Surfaced { line: 6, depth: -2 },
+                ]
+            }),
+            survey_aimed_course(SURFACING.lines(), Surfacing::Allow)
+        );
+        assert_eq!(
+            Ok(900),
+            survey_aimed_course(DATA.lines(), Surfacing::Error).and_then(|r| r.position.product())
+        );
+    }
+
+    #[test]
+    fn it_only_keeps_events_when_surveying() {
+        let plotted = plot_with(AimedSubmarine::new(Surfacing::Allow), SURFACING.lines());
+        assert_eq!(Ok(&[][..]), plotted.as_ref().map(AimedSubmarine::events));
+
+        let surveyed = plot_with(Submarine::surveying(Surfacing::Allow), SURFACING.lines());
+        assert_eq!(
+            Ok(&[
+                Event::Surfaced { line: 3, depth: -2 },
+                Event::Surfaced { line: 5, depth: -2 },
+            ][..]),
+            surveyed.as_ref().map(Submarine::events)
+        );
+    }
+
+    /// An aimed submarine that loses a tenth of its aim, rounding down, whenever it moves forward
+    struct Dragging(Position);
+
+    impl Plotter for Dragging {
+        fn apply(&mut self, _line: usize, instruction: Instruction) -> Result<()> {
+            let Position {
+                depth, across, aim, ..
+            } = &mut self.0;
+
+            match instruction {
+                Instruction(Command::Up, num) => *aim -= i64::from(num),
+                Instruction(Command::Down, num) => *aim += i64::from(num),
+                Instruction(Command::Forward, num) => {
+                    *across += i64::from(num);
+                    *depth += *aim * i64::from(num);
+                    *aim -= *aim / 10;
+                }
+                _ => (),
+            }
+
+            Ok(())
+        }
+
+        fn position(&self) -> Position {
+            self.0
+        }
+    }
+
+    #[test]
+    fn it_plots_with_custom_physics() {
+        let dragging = plot_with(Dragging(Position::default()), DATA.lines()).unwrap();
+
+        assert_eq!(
+            Position {
+                depth: 60,
+                across: 15,
+                lateral: 0,
+                aim: 9
+            },
+            dragging.position()
+        );
+        assert_eq!(Ok(900), dragging.position().product());
+    }
+
+    #[test]
+    fn it_keeps_the_final_state() {
+        let aimed = plot_with_from_reader(AimedSubmarine::new(Surfacing::Error), DATA.as_bytes());
+
+        assert_eq!(
+            Ok(Position {
+                depth: 60,
+                across: 15,
+                lateral: 0,
+                aim: 10
+            }),
+            aimed.as_ref().map(Plotter::position)
+        );
+        assert_eq!(Ok(&[][..]), aimed.as_ref().map(AimedSubmarine::events));
+        assert_eq!(
+            Ok(Instruction(Command::Forward, 15)),
+            Instruction::parse(1, "forward 15")
+        );
+    }
+
+    #[test]
+    fn it_records_the_trajectory() {
+        let (submarine, trajectory) =
+            record(Submarine::new(Surfacing::Error), DATA.lines()).unwrap();
+
+        assert_eq!(Ok(150), submarine.position().product());
+        assert_eq!(
+            "step,depth,across,lateral,aim\n0,0,0,0,0\n1,0,5,0,0\n2,5,5,0,0\n3,5,13,0,0\n4,2,13,0,0\n5,10,13,0,0\n6,10,15,0,0\n",
+            trajectory.to_csv()
+        );
+        assert_eq!(
+            TrajectoryStats {
+                max_depth: 10,
+                max_depth_step: 5,
+                distance: 31.0
+            },
+            trajectory.stats()
+        );
+    }
+
+    #[test]
+    fn it_draws_trajectories() {
+        let (_, course) = record(Submarine::new(Surfacing::Error), DATA.lines()).unwrap();
+        let (_, aimed) =
+            record_from_reader(AimedSubmarine::new(Surfacing::Error), DATA.as_bytes()).unwrap();
+        let svg = to_svg(&[("Part 1", &course), ("Part 2 & aim", &aimed)]);
+
+        assert_eq!(60, aimed.stats().max_depth);
+        assert!(svg.contains(r#"viewBox="0 0 15 60""#));
+        assert!(svg.contains(r#"points="0,0 5,0 5,5 13,5 13,2 13,10 15,10""#));
+        assert!(svg.contains(r#"points="0,0 5,0 5,0 13,40 13,40 13,40 15,60""#));
+        assert!(svg.contains(">Part 2 &amp; aim</text>"));
+        assert!(svg.ends_with("</svg>\n</svg>\n"));
+    }
+
+    const MANOEUVRES: &str = r#"down 2
+forward 3
+right 4
+turn 90
+forward 5
+left 1
+backward 2
+turn 270
+up 1
+forward 1"#;
+
+    #[test]
+    fn it_moves_in_three_dimensions() {
+        let course = survey_course(
+            [
+                "down 4",
+                "forward 3",
+                "right 2",
+                "backward 1",
+                "left 5",
+                "up 1",
+            ]
+            .into_iter(),
+            Surfacing::Error,
+        );
+
+        assert_eq!(
+            Ok(Position {
+                depth: 3,
+                across: 2,
+                lateral: -3,
+                aim: 0
+            }),
+            course.map(|r| r.position)
+        );
+    }
+
+    #[test]
+    fn it_steers_the_aimed_submarine() {
+        let aimed = plot_with(AimedSubmarine::new(Surfacing::Error), MANOEUVRES.lines()).unwrap();
+
+        assert_eq!(
+            Position {
+                depth: 13,
+                across: 5,
+                lateral: 4 + 5 - 2,
+                aim: 1
+            },
+            aimed.position()
+        );
+        assert_eq!(0, aimed.heading());
+    }
+
+    #[test]
+    fn it_only_turns_in_quarters() {
+        assert_eq!(
+            Err(Error::parse(DAY, 1, 6, "45")),
+            plot_aimed_course(["turn 45"].into_iter())
+        );
+        assert_eq!(
+            Err(Error::calc(
+                DAY,
+                "Line 4 turns, but only an aimed submarine can steer"
+            )),
+            plot_course(MANOEUVRES.lines())
+        );
+    }
+
+    #[test]
+    fn it_plans_courses() {
+        let course = |model, across, depth| {
+            synthesize(model, across, depth, Surfacing::Error)
+                .map(|c| c.iter().map(Instruction::to_string).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            Ok(vec!["down 10".into(), "forward 15".into()]),
+            course(Model::Plain, 15, 10)
+        );
+        assert_eq!(
+            Ok(vec!["down 4".into(), "forward 15".into()]),
+            course(Model::Aimed, 15, 60)
+        );
+        assert_eq!(
+            Ok(vec![
+                "down 4".into(),
+                "forward 11".into(),
+                "down 1".into(),
+                "forward 4".into()
+            ]),
+            course(Model::Aimed, 15, 64)
+        );
+        assert_eq!(Ok(vec![]), course(Model::Aimed, 0, 0));
+        assert_eq!(
+            Ok(vec![
+                Instruction(Command::Down, u32::MAX),
+                Instruction(Command::Down, 1)
+            ]),
+            synthesize(Model::Plain, 0, 1 << 32, Surfacing::Error)
+        );
+    }
+
+    #[test]
+    fn it_knows_what_it_cannot_reach() {
+        assert_eq!(
+            Err(Error::calc(
+                DAY,
+                "Can't reach 3 across at depth -1: it's above the surface"
+            )),
+            synthesize(Model::Aimed, 3, -1, Surfacing::Clamp)
+        );
+        assert!(synthesize(Model::Plain, -1, 0, Surfacing::Allow).is_err());
+        assert!(synthesize(Model::Aimed, 0, 5, Surfacing::Allow).is_err());
+    }
+
+    #[test]
+    fn planned_courses_reach_their_targets() {
+        // A fixed xorshift sequence, so any failure can be reproduced
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound) as i64
+        };
+
+        for _ in 0..500 {
+            let (across, depth) = (next(2000), next(2_000_000) - 1_000_000);
+
+            for (model, surfacing) in [
+                (Model::Plain, Surfacing::Error),
+                (Model::Plain, Surfacing::Allow),
+                (Model::Aimed, Surfacing::Error),
+                (Model::Aimed, Surfacing::Allow),
+            ] {
+                let Ok(course) = synthesize(model, across, depth, surfacing) else {
+                    assert!(
+                        depth < 0 && surfacing == Surfacing::Error
+                            || model == Model::Aimed && across == 0,
+                        "{model:?} couldn't reach {across}, {depth}"
+                    );
+                    continue;
+                };
+                let lines = course.iter().map(Instruction::to_string);
+
+                let reached = match model {
+                    Model::Plain => survey_course(lines.clone(), surfacing),
+                    Model::Aimed => survey_aimed_course(lines.clone(), surfacing),
+                }
+                .map(|r| (r.position.across, r.position.depth));
+                assert_eq!(Ok((across, depth)), reached, "{model:?} {course:?}");
+
+                if surfacing == Surfacing::Error {
+                    let product = match model {
+                        Model::Plain => plot_course(lines),
+                        Model::Aimed => plot_aimed_course(lines),
+                    };
+                    assert_eq!(Ok(across * depth), product);
+                }
+            }
+        }
+    }
+}
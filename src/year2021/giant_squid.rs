@@ -0,0 +1,326 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::error::{Error, Result};
+use crate::input::normalize;
+use crate::solution::{prepared, Answer, Prepared, Solution};
+use crate::utils::read_to_string;
+
+const DAY: u8 = 4;
+
+const BOARD_LENGTH: usize = 5;
+
+struct Board {
+    grid: [[u32; BOARD_LENGTH]; BOARD_LENGTH],
+    circled: HashSet<(usize, usize)>,
+    won: bool,
+}
+
+impl std::fmt::Debug for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for x in 0..BOARD_LENGTH {
+            writeln!(f)?;
+            for y in 0..BOARD_LENGTH {
+                if self.circled.contains(&(x, y)) {
+                    write!(f, "({:>2}) ", self.grid[x][y])?;
+                } else {
+                    write!(f, " {:>2}  ", self.grid[x][y])?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Parse a board from `data`, which must be a slice of the whole puzzle `input`
+    fn new(input: &str, data: &str) -> Result<Self> {
+        let mut grid = [[0; BOARD_LENGTH]; BOARD_LENGTH];
+        let mut lines = data.lines();
+
+        for row in &mut grid {
+            // Board not tall enough
+            let line = lines
+                .next()
+                .ok_or_else(|| Error::parse_at(DAY, input, &data[data.len()..]))?;
+            let mut numbers = line.split_whitespace();
+
+            for cell in row {
+                // Board not wide enough
+                let number = numbers
+                    .next()
+                    .ok_or_else(|| Error::parse_at(DAY, input, &line[line.len()..]))?;
+                *cell = number
+                    .parse()
+                    .map_err(|_| Error::parse_at(DAY, input, number))?;
+            }
+            // Board too wide
+            if let Some(extra) = numbers.next() {
+                return Err(Error::parse_at(DAY, input, extra));
+            }
+        }
+        // Board too tall
+        if let Some(extra) = lines.next() {
+            return Err(Error::parse_at(DAY, input, extra.trim_start()));
+        }
+
+        Ok(Board {
+            grid,
+            circled: HashSet::new(),
+            won: false,
+        })
+    }
+
+    fn play_turn(mut self, num: u32) -> (Self, Option<Result<u32>>) {
+        if self.won {
+            return (self, None);
+        }
+
+        if let Some((x, y)) = self
+            .grid
+            .iter()
+            .enumerate()
+            .find_map(|(x, row)| row.iter().position(|n| *n == num).map(|y| (x, y)))
+        {
+            self.circled.insert((x, y));
+
+            if self.check_for_win(x, y) {
+                self.won = true;
+                let score = self.calculate_score(num);
+
+                return (self, Some(score));
+            }
+        }
+
+        (self, None)
+    }
+
+    fn check_for_win(&self, x: usize, y: usize) -> bool {
+        (0..BOARD_LENGTH).all(|i| self.circled.contains(&(x, i)))
+            || (0..BOARD_LENGTH).all(|i| self.circled.contains(&(i, y)))
+    }
+
+    fn calculate_score(&self, winning: u32) -> Result<u32> {
+        let iter = || 0..BOARD_LENGTH;
+
+        iter()
+            .flat_map(|x| {
+                iter()
+                    .filter(move |y| !self.circled.contains(&(x, *y)))
+                    .map(move |y| self.grid[x][y])
+            })
+            .try_fold(0u32, |total, n| total.checked_add(n))
+            .and_then(|total| total.checked_mul(winning))
+            .ok_or_else(too_big)
+    }
+}
+
+fn too_big() -> Error {
+    Error::calc(DAY, "The board's score is too big to count in 32 bits")
+}
+
+struct Boards {
+    boards: Vec<Board>,
+}
+
+impl Boards {
+    fn new(input: &str, data: &[&str]) -> Result<Self> {
+        let boards = data
+            .iter()
+            .map(|b| Board::new(input, b))
+            .collect::<Result<_>>()?;
+        Ok(Self { boards })
+    }
+
+    fn play_number(mut self, num: u32) -> (Self, Vec<Result<u32>>) {
+        let (boards, scores): (Vec<Board>, Vec<Option<Result<u32>>>) =
+            self.boards.into_iter().map(|b| b.play_turn(num)).unzip();
+
+        self.boards = boards;
+
+        (self, scores.into_iter().flatten().collect())
+    }
+}
+
+struct Bingo {
+    numbers: Vec<u32>,
+    boards: Boards,
+}
+
+impl Bingo {
+    fn new(input: &str) -> Result<Self> {
+        let input = &*normalize(input);
+        let mut data = input.split("\n\n");
+
+        let numbers = data
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .split(',')
+            .map(|s| s.parse().map_err(|_| Error::parse_at(DAY, input, s)))
+            .collect::<Result<_>>()?;
+
+        let boards = Boards::new(input, &data.collect::<Vec<_>>())?;
+
+        Ok(Bingo { numbers, boards })
+    }
+
+    fn play(self) -> Vec<Result<u32>> {
+        let (_, scores) =
+            self.numbers
+                .into_iter()
+                .fold((self.boards, Vec::new()), |(boards, mut scores), n| {
+                    let (boards, mut scrs) = boards.play_number(n);
+                    scores.append(&mut scrs);
+                    (boards, scores)
+                });
+
+        scores
+    }
+}
+
+fn first_score(bingo: Bingo) -> Result<u32> {
+    match bingo.play().into_iter().next() {
+        Some(head) => head,
+        _ => Err(Error::calc(DAY, "No board won")),
+    }
+}
+
+fn last_score(bingo: Bingo) -> Result<u32> {
+    match bingo.play().pop() {
+        Some(last) => last,
+        _ => Err(Error::calc(DAY, "No board won")),
+    }
+}
+
+pub fn first_winning_board_score(data: &str) -> Result<u32> {
+    first_score(Bingo::new(data)?)
+}
+
+pub fn last_winning_board_score(data: &str) -> Result<u32> {
+    last_score(Bingo::new(data)?)
+}
+
+pub fn first_winning_board_score_from_reader<R: BufRead>(reader: R) -> Result<u32> {
+    first_winning_board_score(&read_to_string(reader)?)
+}
+
+pub fn last_winning_board_score_from_reader<R: BufRead>(reader: R) -> Result<u32> {
+    last_winning_board_score(&read_to_string(reader)?)
+}
+
+pub struct GiantSquid;
+
+impl Solution for GiantSquid {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Giant Squid"
+    }
+
+    fn name(&self) -> &'static str {
+        "giant_squid"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        first_winning_board_score(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        last_winning_board_score(input).map(Into::into)
+    }
+
+    fn prepare(&self, input: &str) -> Option<Result<Prepared>> {
+        Some(Bingo::new(input).map(|bingo| {
+            prepared(
+                DAY,
+                bingo,
+                |b| first_score(b).map(Into::into),
+                |b| last_score(b).map(Into::into),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7"#;
+
+    #[test]
+    fn it_calculates_first_winning_board_score() {
+        assert_eq!(Ok(4512), first_winning_board_score(DATA));
+    }
+
+    #[test]
+    fn it_calculates_last_winning_board_score() {
+        assert_eq!(Ok(1924), last_winning_board_score(DATA));
+    }
+
+    #[test]
+    fn it_reports_malformed_boards() {
+        assert_eq!(
+            Err(Error::parse(DAY, 5, 10, "x")),
+            first_winning_board_score(&DATA.replacen("14 16", "14 x", 1))
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 4, 12, "")),
+            first_winning_board_score(&DATA.replacen(" 24\n", "\n", 1))
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 4, 16, "junk")),
+            first_winning_board_score(&DATA.replacen(" 24\n", " 24 junk\n", 1))
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 8, 1, "junk")),
+            last_winning_board_score(&DATA.replacen("15 19\n", "15 19\njunk\n", 1))
+        );
+    }
+
+    #[test]
+    fn it_reports_scores_too_big_to_count() {
+        let huge = ["4000000000"; BOARD_LENGTH].join(" ");
+        let data = format!(
+            "1,2,3,4,5\n\n1 2 3 4 5\n{}",
+            [huge.as_str(); BOARD_LENGTH - 1].join("\n")
+        );
+
+        assert_eq!(Err(too_big()), first_winning_board_score(&data));
+        assert_eq!(Err(too_big()), last_winning_board_score(&data));
+    }
+
+    #[test]
+    fn it_only_reports_the_score_it_was_asked_for() {
+        let huge = ["4000000000"; BOARD_LENGTH].join(" ");
+        let data = format!(
+            "1,2,3,4,5,6\n\n1 2 3 4 5\n{small}\n\n2 3 4 5 6\n{huge}",
+            small = ["10 10 10 10 10"; BOARD_LENGTH - 1].join("\n"),
+            huge = [huge.as_str(); BOARD_LENGTH - 1].join("\n")
+        );
+
+        assert_eq!(Ok(1000), first_winning_board_score(&data));
+        assert_eq!(Err(too_big()), last_winning_board_score(&data));
+    }
+}
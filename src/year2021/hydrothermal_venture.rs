@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::iter::{from_fn, repeat, zip};
 
 use nom::branch::alt;
@@ -10,10 +11,15 @@ use nom::multi::many0;
 use nom::sequence::{separated_pair, terminated};
 use nom::{IResult, Parser};
 
-use crate::utils::DivCeil;
+use crate::error::{Error, Result};
+use crate::input::{self, normalize};
+use crate::solution::{prepared, Answer, Prepared, Solution};
+use crate::utils::read_to_string;
 
-/// This is totally overengineered, but it was worth it. I learned a TON about nom parser
-/// combinators, and about working in 2d space.
+const DAY: u8 = 5;
+
+// This is totally overengineered, but it was worth it. I learned a TON about nom parser
+// combinators, and about working in 2d space.
 
 fn separated_twins<F, G, H, I, O1, O2>(f: F, sep: H) -> impl FnMut(I) -> IResult<I, (O1, O1)>
 where
@@ -63,7 +69,6 @@ impl Line {
     }
 }
 
-#[allow(unstable_name_collisions)]
 fn diagonal_ranges(x1: u32, x2: u32, y1: u32, y2: u32) -> impl Iterator<Item = (u32, u32)> {
     let diff_x = x2.abs_diff(x1);
     let diff_y = y2.abs_diff(y1);
@@ -74,10 +79,13 @@ fn diagonal_ranges(x1: u32, x2: u32, y1: u32, y2: u32) -> impl Iterator<Item = (
         _ => (1, 1),
     };
 
+    // A line is as many points long as its longest axis, which also stops a single point
+    // line (where both axes repeat forever) after one point
     zip(
         make_range_stretched(x1, x2, delta_x),
         make_range_stretched(y1, y2, delta_y),
     )
+    .take((diff_x.max(diff_y) as usize).saturating_add(1))
 }
 
 fn make_range_stretched(start: u32, end: u32, delta: u32) -> Box<dyn Iterator<Item = u32>> {
@@ -111,27 +119,24 @@ where
 fn range_multidirectional(start: u32, end: u32) -> Box<dyn Iterator<Item = u32>> {
     match start.cmp(&end) {
         Ordering::Equal => Box::new(repeat(start)),
-        Ordering::Greater => Box::new(range_desc_incl(start, end)),
+        Ordering::Greater => Box::new((end..=start).rev()),
         Ordering::Less => Box::new(start..=end),
     }
 }
 
-fn range_desc_incl(start: u32, end: u32) -> impl Iterator<Item = u32> {
-    let mut i = start + 1;
-    from_fn(move || {
-        i = i.checked_sub(1)?;
-        if i < end {
-            None
-        } else {
-            Some(i)
-        }
-    })
-}
-
 fn vent_parser(input: &str) -> IResult<&str, Vec<Line>> {
     many0(terminated(Line::parse, alt((line_ending, eof))))(input)
 }
 
+/// Parse every vent line, complaining about anything that isn't one
+fn parse_vents(data: &str) -> Result<Vec<Line>> {
+    let data = &*normalize(data);
+    let (rest, lines) = vent_parser(data).map_err(|e| Error::nom(DAY, data, e))?;
+    input::finish(DAY, data, rest)?;
+
+    Ok(lines)
+}
+
 fn dangerous_points<I: Iterator<Item = Line>>(lines: I) -> usize {
     lines
         .flat_map(|l| l.points())
@@ -143,25 +148,68 @@ fn dangerous_points<I: Iterator<Item = Line>>(lines: I) -> usize {
 
             danger_register
         })
-        .into_iter()
-        .filter_map(|(_, v)| (v > 1).then_some(v))
+        .into_values()
+        .filter(|v| *v > 1)
         .count()
 }
 
-pub fn right_angle_dangerous_points(
-    data: &str,
-) -> Result<usize, nom::Err<nom::error::Error<&str>>> {
-    let (_, lines) = vent_parser(data)?;
-    let danger = dangerous_points(lines.into_iter().filter(|l| !l.is_diagonal()));
+fn right_angle_danger(lines: Vec<Line>) -> usize {
+    dangerous_points(lines.into_iter().filter(|l| !l.is_diagonal()))
+}
+
+fn all_danger(lines: Vec<Line>) -> usize {
+    dangerous_points(lines.into_iter())
+}
+
+pub fn right_angle_dangerous_points(data: &str) -> Result<usize> {
+    Ok(right_angle_danger(parse_vents(data)?))
+}
+
+pub fn all_dangerous_points(data: &str) -> Result<usize> {
+    Ok(all_danger(parse_vents(data)?))
+}
+
+pub fn right_angle_dangerous_points_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    right_angle_dangerous_points(&read_to_string(reader)?)
+}
 
-    Ok(danger)
+pub fn all_dangerous_points_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    all_dangerous_points(&read_to_string(reader)?)
 }
 
-pub fn all_dangerous_points(data: &str) -> Result<usize, nom::Err<nom::error::Error<&str>>> {
-    let (_, lines) = vent_parser(data)?;
-    let danger = dangerous_points(lines.into_iter());
+pub struct HydrothermalVenture;
+
+impl Solution for HydrothermalVenture {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Hydrothermal Venture"
+    }
+
+    fn name(&self) -> &'static str {
+        "hydrothermal_venture"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        right_angle_dangerous_points(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        all_dangerous_points(input).map(Into::into)
+    }
 
-    Ok(danger)
+    fn prepare(&self, input: &str) -> Option<Result<Prepared>> {
+        Some(parse_vents(input).map(|lines| {
+            prepared(
+                DAY,
+                lines,
+                |l| Ok(right_angle_danger(l).into()),
+                |l| Ok(all_danger(l).into()),
+            )
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -395,6 +443,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_plots_a_single_point_line_once() {
+        let (_, line) = Line::parse("1,1 -> 1,1").unwrap();
+        assert_eq!(
+            vec![Point { x: 1, y: 1 }],
+            line.points().collect::<Vec<_>>()
+        );
+        assert_eq!(Ok(1), all_dangerous_points("1,1 -> 1,1\n0,0 -> 2,2"));
+    }
+
+    #[test]
+    fn it_plots_lines_at_the_edge_of_the_grid() {
+        let (_, line) = Line::parse("4294967295,0 -> 4294967290,0").unwrap();
+        assert_eq!(
+            (4294967290..=u32::MAX)
+                .rev()
+                .map(|x| Point { x, y: 0 })
+                .collect::<Vec<_>>(),
+            line.points().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Ok(2),
+            right_angle_dangerous_points(
+                "4294967295,0 -> 4294967290,0\n4294967295,0 -> 4294967294,0"
+            )
+        );
+    }
+
     #[test]
     fn it_calculates_dangerous_points() {
         assert_eq!(Ok(5), right_angle_dangerous_points(DATA));
@@ -404,4 +480,16 @@ mod tests {
     fn it_calculates_all_dangerous_points() {
         assert_eq!(Ok(12), all_dangerous_points(DATA));
     }
+
+    #[test]
+    fn it_reports_lines_it_cannot_parse() {
+        assert_eq!(
+            Ok(5),
+            right_angle_dangerous_points(&format!("{}\r\n\r\n", DATA.replace('\n', "\r\n")))
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 1, "8,0 => 0,8")),
+            all_dangerous_points(&DATA.replace("8,0 -> 0,8", "8,0 => 0,8"))
+        );
+    }
 }
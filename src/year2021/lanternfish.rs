@@ -0,0 +1,358 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{BufRead, Split};
+use std::iter::Peekable;
+
+use nom::character::complete::char;
+use nom::character::complete::digit1;
+use nom::combinator::map_res;
+use nom::multi::separated_list1;
+
+use crate::error::{Error, Result};
+use crate::input::{self, normalize, BOM};
+use crate::solution::{prepared, Answer, Prepared, Solution};
+
+const DAY: u8 = 6;
+
+/// Add `count` to the count for `key`, or `None` if it no longer fits in 64 bits
+fn insert_or_update_count<K>(
+    mut map: HashMap<K, u64>,
+    key: K,
+    count: u64,
+) -> Option<HashMap<K, u64>>
+where
+    K: Eq + Hash,
+{
+    match map.entry(key) {
+        Entry::Occupied(mut entry) => *entry.get_mut() = entry.get().checked_add(count)?,
+        Entry::Vacant(entry) => {
+            entry.insert(count);
+        }
+    }
+    Some(map)
+}
+
+fn too_many() -> Error {
+    Error::calc(DAY, "There are too many fish to count in 64 bits")
+}
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct Fish {
+    age: u32,
+}
+
+impl Fish {
+    fn born() -> Self {
+        Self { age: 8 }
+    }
+
+    fn sim_day(mut self) -> (Self, Option<Self>) {
+        if self.age == 0 {
+            self.age = 6;
+            (self, Some(Fish::born()))
+        } else {
+            self.age -= 1;
+            (self, None)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct School {
+    day: u32,
+    fish: HashMap<Fish, u64>,
+}
+
+impl School {
+    fn new(fish: &[u32]) -> Self {
+        let mut school = Self {
+            day: 0,
+            fish: HashMap::with_capacity(9),
+        };
+        for age in fish {
+            school.add(Fish { age: *age }, 1);
+        }
+        school
+    }
+
+    fn add(&mut self, fish: Fish, count: u64) {
+        self.fish
+            .entry(fish)
+            .and_modify(|c| *c += count)
+            .or_insert(count);
+    }
+
+    fn sim_day(mut self) -> Option<Self> {
+        self.day += 1;
+        self.fish =
+            self.fish
+                .into_iter()
+                .try_fold(HashMap::with_capacity(9), |mut fs, (f, count)| {
+                    let (daddy_fish, maybe_baby) = f.sim_day();
+
+                    fs = insert_or_update_count(fs, daddy_fish, count)?;
+
+                    if let Some(baby_fish) = maybe_baby {
+                        fs = insert_or_update_count(fs, baby_fish, count)?;
+                    }
+
+                    Some(fs)
+                })?;
+
+        Some(self)
+    }
+
+    fn total_fish(&self) -> Option<u64> {
+        self.fish
+            .values()
+            .try_fold(0u64, |total, count| total.checked_add(*count))
+    }
+}
+
+fn parse_fish(data: &str) -> Result<Vec<u32>> {
+    let data = &*normalize(data);
+    let (rest, fish) = separated_list1(char(','), map_res(digit1, str::parse::<u32>))(data)
+        .map_err(|e| Error::nom(DAY, data, e))?;
+    input::finish(DAY, data, rest)?;
+
+    Ok(fish)
+}
+
+fn simulate(mut school: School, days: u32) -> Result<u64> {
+    for _ in 0..days {
+        school = school.sim_day().ok_or_else(too_many)?;
+    }
+    school.total_fish().ok_or_else(too_many)
+}
+
+pub fn simulate_fish(data: &str, days: u32) -> Result<u64> {
+    let fish = parse_fish(data)?;
+    simulate(School::new(&fish), days)
+}
+
+/// Simulate the fish while reading their ages one at a time, so only the counts are kept in memory.
+/// This accepts and rejects the same input as [`simulate_fish`], with errors in the same places.
+pub fn simulate_fish_from_reader<R: BufRead>(reader: R, days: u32) -> Result<u64> {
+    let mut school = School::new(&[]);
+    let mut column = 1;
+    let mut ages = reader.split(b',').peekable();
+
+    while let Some(age) = ages.next() {
+        let age = age?;
+        let text = String::from_utf8_lossy(&age);
+        let text = match column {
+            1 => text.strip_prefix(BOM).unwrap_or(&text),
+            _ => &text,
+        };
+        // The ages are all on the first line, so the last one ends there, and only it gets the
+        // line's trailing whitespace trimmed off
+        let (text, rest) = match text.split_once('\n') {
+            Some((text, rest)) => (text.trim_end(), Some(rest)),
+            None if ages.peek().is_none() => (text.trim_end(), None),
+            None => (text, None),
+        };
+        let ended = rest.is_some();
+
+        let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        match text[..digits].parse() {
+            Ok(age) => school.add(Fish { age }, 1),
+            // Like the parser, the first age is reported where it starts, and later ones where
+            // the comma before them is
+            Err(_) if column == 1 => return Err(unparsed(column, text, &mut ages, ended)?),
+            Err(_) => {
+                return Err(unparsed(column - 1, &format!(",{text}"), &mut ages, ended)?);
+            }
+        }
+
+        let after = &text[digits..];
+        if !after.is_empty() {
+            let skipped = after.len() - after.trim_start().len();
+            let column = column + digits + after[..skipped].chars().count();
+            return Err(unparsed(column, after.trim_start(), &mut ages, ended)?);
+        }
+        column += text.chars().count() + 1;
+
+        if let Some(rest) = rest {
+            // Keep the newline that ended the first line, so leftovers are reported on theirs
+            let mut rest = format!("\n{rest}");
+            for age in ages.by_ref() {
+                rest.push(',');
+                rest.push_str(&String::from_utf8_lossy(&age?));
+            }
+            input::finish(DAY, &rest, &rest)?;
+        }
+    }
+
+    if school.fish.is_empty() {
+        return Err(Error::parse(DAY, 1, column, ""));
+    }
+
+    simulate(school, days)
+}
+
+/// The error for the first line being left unparsed from `column`, which starts with `text`.
+/// Unless the line has `ended`, the rest of it is read from `ages` to finish the error's text.
+fn unparsed<R: BufRead>(
+    column: usize,
+    text: &str,
+    ages: &mut Peekable<Split<R>>,
+    ended: bool,
+) -> Result<Error> {
+    let mut text = text.to_string();
+    if !ended {
+        for age in ages {
+            let age = String::from_utf8_lossy(&age?).into_owned();
+            text.push(',');
+            match age.split_once('\n') {
+                Some((age, _)) => {
+                    text.push_str(age);
+                    break;
+                }
+                None => text.push_str(&age),
+            }
+        }
+    }
+
+    Ok(Error::parse(DAY, 1, column, text.trim_end()))
+}
+
+pub struct Lanternfish;
+
+impl Solution for Lanternfish {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Lanternfish"
+    }
+
+    fn name(&self) -> &'static str {
+        "lanternfish"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        simulate_fish(input, 80).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        simulate_fish(input, 256).map(Into::into)
+    }
+
+    fn solve_reader(&self, part: u8, reader: &mut dyn BufRead) -> Result<Answer> {
+        match part {
+            1 => simulate_fish_from_reader(reader, 80).map(Into::into),
+            2 => simulate_fish_from_reader(reader, 256).map(Into::into),
+            _ => Err(Error::NoSuchPart { day: DAY, part }),
+        }
+    }
+
+    fn prepare(&self, input: &str) -> Option<Result<Prepared>> {
+        Some(parse_fish(input).map(|fish| {
+            prepared(
+                DAY,
+                fish,
+                |f| simulate(School::new(&f), 80).map(Into::into),
+                |f| simulate(School::new(&f), 256).map(Into::into),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "3,4,3,1,2";
+
+    #[test]
+    fn test_simulate_fish() -> Result<()> {
+        let dataset = [
+            ("small", 18, 26),
+            ("big", 80, 5_934),
+            ("huge", 256, 26_984_457_539),
+        ];
+
+        for (tag, days, expected) in dataset {
+            println!("{tag}");
+            assert_eq!(expected, simulate_fish(DATA, days)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_schools_too_big_to_count() {
+        assert_eq!(Err(too_many()), simulate_fish(DATA, 1_000));
+        assert_eq!(
+            Err(too_many()),
+            simulate_fish_from_reader(DATA.as_bytes(), u32::MAX)
+        );
+    }
+
+    #[test]
+    fn it_reports_missing_fish() {
+        assert_eq!(Err(Error::parse(DAY, 1, 1, "x,3")), simulate_fish("x,3", 1));
+    }
+
+    #[test]
+    fn it_reports_leftover_input() {
+        assert_eq!(Ok(26), simulate_fish("\u{feff}3,4,3,1,2\r\n\r\n", 18));
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 1, "junk")),
+            simulate_fish("3,4,3,1,2\njunk\n", 18)
+        );
+        assert_eq!(
+            Ok(26),
+            simulate_fish_from_reader("\u{feff}3,4,3,1,2\r\n".as_bytes(), 18)
+        );
+
+        for data in [
+            "3,4\njunk\n",
+            "3,4\r\n\r\n  5,6",
+            "3\n,4",
+            "3,4\n\n",
+            "3,4 ,5",
+            "3,4,\n",
+            "3, 4",
+            "3,4x,5\n",
+            "x,3",
+            ",3",
+            "3,4,x,1",
+            "3,99999999999,1",
+            "99999999999,1",
+            "3,4  \r\n",
+            "\n3",
+        ] {
+            assert_eq!(
+                simulate_fish(data, 18),
+                simulate_fish_from_reader(data.as_bytes(), 18),
+                "{data:?}"
+            );
+        }
+        assert_eq!(
+            Err(Error::parse(DAY, 1, 5, ",5")),
+            simulate_fish_from_reader("3,4 ,5".as_bytes(), 18)
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 3, 3, "5,6")),
+            simulate_fish_from_reader("3,4\r\n\r\n  5,6".as_bytes(), 18)
+        );
+    }
+
+    #[test]
+    fn it_simulates_fish_from_a_reader() {
+        assert_eq!(
+            Ok(5_934),
+            simulate_fish_from_reader(b"3,4,3,1,2\n".as_slice(), 80)
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 1, 4, ",x,1")),
+            simulate_fish_from_reader(b"3,4,x,1".as_slice(), 80)
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 1, 1, "")),
+            simulate_fish_from_reader(b"".as_slice(), 80)
+        );
+    }
+}
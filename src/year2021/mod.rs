@@ -0,0 +1,22 @@
+pub mod binary_diagnostic;
+pub mod dive;
+pub mod giant_squid;
+pub mod hydrothermal_venture;
+pub mod lanternfish;
+pub mod seven_segment_search;
+pub mod sonar_sweep;
+pub mod the_treachery_of_whales;
+
+use crate::solution::Solution;
+
+/// Every solved day of 2021, in order
+pub const DAYS: &[&dyn Solution] = &[
+    &sonar_sweep::SonarSweep,
+    &dive::Dive,
+    &binary_diagnostic::BinaryDiagnostic,
+    &giant_squid::GiantSquid,
+    &hydrothermal_venture::HydrothermalVenture,
+    &lanternfish::Lanternfish,
+    &the_treachery_of_whales::TheTreacheryOfWhales,
+    &seven_segment_search::SevenSegmentSearch,
+];
@@ -2,6 +2,14 @@ use std::borrow::Borrow;
 use std::cmp::Reverse;
 use std::collections::BTreeSet;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::io::BufRead;
+
+use crate::error::{Error, Result};
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use crate::utils::read_to_string;
+
+const DAY: u8 = 8;
 
 /// Corresponds to digits 1, 4, 7, and 8
 const DIGITS_WITH_UNIQUE_NUMBER_SEGMENTS: [usize; 4] = [2, 4, 3, 7];
@@ -205,33 +213,30 @@ struct Pattern {
 }
 
 impl Pattern {
-    fn parse(data: &str) -> Option<Self> {
-        if data.is_empty() {
-            return None;
-        }
-
-        let wires = data.chars().filter_map(Wire::parse).collect();
-
-        Some(Self { wires })
+    /// Parse a pattern from `data`, which must be a slice of the whole puzzle `input`
+    fn parse(input: &str, data: &str) -> Result<Self> {
+        let wires = data
+            .char_indices()
+            .map(|(i, c)| Wire::parse(c).ok_or_else(|| Error::parse_at(DAY, input, &data[i..])))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { wires })
     }
 
     fn len(&self) -> usize {
         self.wires.len()
     }
 
-    fn decode(&self, solution: &[(Wire, Signal)]) -> usize {
+    fn decode(&self, solution: &[(Wire, Signal)]) -> Option<usize> {
         let signals: Vec<_> = self
             .wires
             .iter()
             .filter_map(|wire| solution.iter().find_map(|(w, s)| (w == wire).then_some(s)))
             .collect();
 
-        digit_patterns()
-            .iter()
-            .find_map(|(digit, pattern)| {
-                slice_eq(&pattern[..], &signals[..]).then_some(*digit as usize)
-            })
-            .expect("The solution should work to find a digit")
+        digit_patterns().iter().find_map(|(digit, pattern)| {
+            slice_eq(&pattern[..], &signals[..]).then_some(*digit as usize)
+        })
     }
 }
 
@@ -321,11 +326,10 @@ impl Solver {
 
     /// Figure out which signal corresponds to which wire, based on the given observed pattern samples
     fn solve(&mut self, samples: &[Pattern]) -> Option<Vec<(Wire, Signal)>> {
-        eprintln!("{}", &self);
         loop {
-            for sample in samples {
-                self.changed = false;
+            self.changed = false;
 
+            for sample in samples {
                 // Find all digit patterns that have the same length as this sample
                 let matched_patterns: Vec<_> = digit_patterns()
                     .into_iter()
@@ -335,18 +339,6 @@ impl Solver {
 
                 let wires = &sample.wires;
                 if let Some(pattern) = self.deduce_pattern(wires, matched_patterns) {
-                    eprintln!(
-                        "Wires   : '{}'",
-                        &wires.iter().map(Wire::to_string).collect::<String>()
-                    );
-                    eprintln!(
-                        "Pattern : '{}'",
-                        &pattern
-                            .iter()
-                            .map(Signal::to_string)
-                            .collect::<Vec<_>>()
-                            .join(",")
-                    );
                     self.deduce(wires, pattern);
                 }
 
@@ -408,28 +400,34 @@ impl Solver {
                 self.mark_known(*unsolved_wires[0], signal);
             }
         }
-
-        eprintln!("{}", &self);
     }
 
     /// Add a wire/signal pair to the solution vec and remove all invalidated possibilities
-    /// Also marks the Solver struct as changed for this iteration of the main solve loop
+    /// Also marks the Solver struct as changed for this iteration of the main solve loop, if
+    /// anything was removed
     fn mark_known(&mut self, wire: Wire, signal: Signal) {
+        let before = self.guesses.len();
         self.guesses
             .retain(|(w, s)| matches!((*w != wire, *s != signal), (true, true) | (false, false)));
-        self.mark_changed();
+        if self.guesses.len() < before {
+            self.mark_changed();
+        }
     }
 
     /// Cross off possibilities that we know are invalid
-    /// Also marks the Solver struct as changed for this iteration of the main solve loop
+    /// Also marks the Solver struct as changed for this iteration of the main solve loop, if
+    /// anything was crossed off
     fn narrow_guesses(&mut self, wires: &[Wire], signals: &[Signal]) {
+        let before = self.guesses.len();
         self.guesses.retain(|(w, s)| {
             matches!(
                 (wires.contains(w), signals.contains(s)),
                 (true, true) | (false, false)
             )
         });
-        self.mark_changed();
+        if self.guesses.len() < before {
+            self.mark_changed();
+        }
     }
 
     /// Mark the struct as changed and recalculate the groups
@@ -522,29 +520,37 @@ impl Solver {
 
 macro_rules! samples_signals {
     ($data:ident) => {
-        $data.lines().filter_map(|l| l.split_once(" | "))
+        $data.lines().enumerate().map(|(i, l)| {
+            l.split_once('|')
+                .ok_or_else(|| Error::parse(DAY, i + 1, 1, l))
+        })
     };
 }
 
 /// Get the total number of signal patterns with unique sizes in the data
-pub fn unique_segment_total(data: &str) -> usize {
+pub fn unique_segment_total(data: &str) -> Result<usize> {
+    let data = &*normalize(data);
     samples_signals!(data)
         .map(|ps| {
-            ps.1.split_ascii_whitespace()
+            Ok(ps?
+                .1
+                .split_ascii_whitespace()
                 .filter(|p| DIGITS_WITH_UNIQUE_NUMBER_SEGMENTS.contains(&p.len()))
-                .count()
+                .count())
         })
         .sum()
 }
 
 /// Figure out what each wire corresponds to and decode the scrambled digits
-pub fn solve_segments(data: &str) -> usize {
+pub fn solve_segments(data: &str) -> Result<usize> {
+    let data = &*normalize(data);
     samples_signals!(data)
-        .filter_map(|(samples, signals)| {
+        .map(|ps| {
+            let (samples, signals) = ps?;
             let mut samples: Vec<_> = samples
                 .split_ascii_whitespace()
-                .filter_map(Pattern::parse)
-                .collect();
+                .map(|p| Pattern::parse(data, p))
+                .collect::<Result<_>>()?;
 
             // Sort the segments, first by uniqueness of length (unique first), then by length
             samples.sort_unstable_by_key(|s| {
@@ -554,36 +560,77 @@ pub fn solve_segments(data: &str) -> usize {
                 )
             });
 
-            let mut solver = Solver::new();
+            let signals: Vec<_> = signals
+                .split_ascii_whitespace()
+                .map(|p| Pattern::parse(data, p))
+                .collect::<Result<_>>()?;
 
-            if let Some(solution) = solver.solve(&samples) {
-                return signals
-                    .split_ascii_whitespace()
-                    .filter_map(Pattern::parse)
-                    .map(|p| p.decode(&solution))
-                    .rev()
-                    .fold((0, 1), |(acc, column), digit| {
-                        (acc + (digit * column), column * 10)
-                    })
-                    .0
-                    .into();
-            }
+            let mut solver = Solver::new();
+            let solution = solver
+                .solve(&samples)
+                .ok_or_else(|| Error::calc(DAY, "Couldn't work out which wire is which"))?;
 
-            None
+            let digits = signals
+                .iter()
+                .map(|p| {
+                    p.decode(&solution)
+                        .ok_or_else(|| Error::calc(DAY, "The solution didn't decode a digit"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(digits
+                .into_iter()
+                .rev()
+                .fold((0, 1), |(acc, column), digit| {
+                    (acc + (digit * column), column * 10)
+                })
+                .0)
         })
         .sum()
 }
 
+pub fn unique_segment_total_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    unique_segment_total(&read_to_string(reader)?)
+}
+
+pub fn solve_segments_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    solve_segments(&read_to_string(reader)?)
+}
+
+pub struct SevenSegmentSearch;
+
+impl Solution for SevenSegmentSearch {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Seven Segment Search"
+    }
+
+    fn name(&self) -> &'static str {
+        "seven_segment_search"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        unique_segment_total(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        solve_segments(input).map(Into::into)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn decode_pattern_using_solution() {
-        let pattern = Pattern::parse("cefbgd").unwrap();
+        let pattern = Pattern::parse("cefbgd", "cefbgd").unwrap();
 
         assert_eq!(
-            9,
+            Some(9),
             pattern.decode(&[
                 (Wire::A, Signal::BottomLeft),
                 (Wire::B, Signal::TopRight),
@@ -716,12 +763,35 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
     #[test]
     fn test_unique_segment_total() {
-        assert_eq!(26, unique_segment_total(DATA));
+        assert_eq!(Ok(26), unique_segment_total(DATA));
     }
 
     #[test]
     fn test_solve_segments() {
-        assert_eq!(5353, solve_segments(SMALL_DATA));
-        assert_eq!(61229, solve_segments(DATA));
+        assert_eq!(Ok(5353), solve_segments(SMALL_DATA));
+        assert_eq!(Ok(61229), solve_segments(DATA));
+    }
+
+    #[test]
+    fn it_reports_unknown_wires() {
+        assert_eq!(
+            Err(Error::parse(DAY, 1, 12, "xdab")),
+            solve_segments("ab cdfbe | xdab")
+        );
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 1, "ab cdfbe")),
+            unique_segment_total("ab | ab\nab cdfbe")
+        );
+    }
+
+    #[test]
+    fn it_reports_entries_too_short_to_solve() {
+        let unsolvable = Err(Error::calc(DAY, "Couldn't work out which wire is which"));
+
+        assert_eq!(unsolvable, solve_segments("ab | ab"));
+        assert_eq!(
+            unsolvable,
+            solve_segments("be cfbegad cbdgef fgaecd cgeb fdcge | fdgacbe cefdb cefbgd gcbe")
+        );
     }
 }
@@ -0,0 +1,442 @@
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::io::BufRead;
+
+use itertools::process_results;
+
+use crate::error::{Error, Result};
+use crate::input::normalize_lines;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 1;
+
+macro_rules! lines_to_ints {
+    ($data:ident) => {
+        normalize_lines($data).enumerate().map(|(i, l)| {
+            let l = l.as_ref();
+            if l.trim().is_empty() {
+                return Err(Error::blank(DAY, i + 1));
+            }
+            l.trim()
+                .parse::<u32>()
+                .map_err(|_| Error::parse(DAY, i + 1, 1, l))
+        })
+    };
+}
+
+fn check_window(window: usize) -> Result<()> {
+    if window == 0 {
+        return Err(Error::calc(
+            DAY,
+            "The window has to hold at least one depth",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Neighbouring windows share all but their first and last depth, so comparing those two is
+/// enough, and only the last `window` depths are kept around
+fn count_increases<I: Iterator<Item = u32>>(depths: I, window: usize) -> usize {
+    let mut ring = Vec::with_capacity(window);
+    let mut oldest = 0;
+    let mut increases = 0;
+
+    for depth in depths {
+        if ring.len() < window {
+            ring.push(depth);
+            continue;
+        }
+
+        if depth > ring[oldest] {
+            increases += 1;
+        }
+        ring[oldest] = depth;
+        oldest = (oldest + 1) % window;
+    }
+
+    increases
+}
+
+/// Count how often the sum of a sliding window of depths goes up. This is strict, the first line
+/// that isn't a depth is an error.
+pub fn sweep_window_increases_n<I, S>(data: I, window: usize) -> Result<usize>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    check_window(window)?;
+    process_results(lines_to_ints!(data), |depths| {
+        count_increases(depths, window)
+    })
+}
+
+/// The outcome of a lenient sweep
+#[derive(Debug, PartialEq, Eq)]
+pub struct LenientSweep {
+    pub increases: usize,
+    /// Every line that was left out, as the error a strict sweep would have stopped at
+    pub skipped: Vec<Error>,
+}
+
+/// Count window increases like [`sweep_window_increases_n`], but skip lines that aren't depths
+/// instead of giving up, so a truncated log still gets counted
+pub fn sweep_window_increases_lenient<I, S>(data: I, window: usize) -> Result<LenientSweep>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    check_window(window)?;
+
+    let mut skipped = Vec::new();
+    let depths = lines_to_ints!(data).filter_map(|depth| depth.map_err(|e| skipped.push(e)).ok());
+    let increases = count_increases(depths, window);
+
+    Ok(LenientSweep { increases, skipped })
+}
+
+pub fn sweep_increases<I, S>(data: I) -> Result<usize>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    sweep_window_increases_n(data, 1)
+}
+
+pub fn sweep_window_increases<I, S>(data: I) -> Result<usize>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    sweep_window_increases_n(data, 3)
+}
+
+/// Count increases while reading depths one line at a time
+pub fn sweep_increases_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    process_results(reader.lines(), |lines| sweep_increases(lines))?
+}
+
+/// Count window increases while reading depths one line at a time
+pub fn sweep_window_increases_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    process_results(reader.lines(), |lines| sweep_window_increases(lines))?
+}
+
+/// Count increases leniently while reading depths one line at a time. Only failing to read is
+/// an error.
+pub fn sweep_window_increases_lenient_from_reader<R: BufRead>(
+    reader: R,
+    window: usize,
+) -> Result<LenientSweep> {
+    process_results(reader.lines(), |lines| {
+        sweep_window_increases_lenient(lines, window)
+    })?
+}
+
+/// Count increases of any window size while reading depths one line at a time
+pub fn sweep_window_increases_n_from_reader<R: BufRead>(reader: R, window: usize) -> Result<usize> {
+    process_results(reader.lines(), |lines| {
+        sweep_window_increases_n(lines, window)
+    })?
+}
+
+/// A stretch of the depth series, by index, with both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+}
+
+// A run always holds at least one depth
+#[allow(clippy::len_without_is_empty)]
+impl Run {
+    pub fn len(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// A change between two neighbouring depths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    /// Index of the depth the jump lands on
+    pub index: usize,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// The depth series of a sweep, for looking at how a dive went rather than just counting
+/// increases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthProfile {
+    depths: Vec<u32>,
+}
+
+impl DepthProfile {
+    pub fn parse<I, S>(data: I) -> Result<Self>
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let depths = lines_to_ints!(data).collect::<Result<_>>()?;
+        Ok(Self { depths })
+    }
+
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        process_results(reader.lines(), |lines| Self::parse(lines))?
+    }
+
+    pub fn depths(&self) -> &[u32] {
+        &self.depths
+    }
+
+    /// Indices of every depth that's deeper than the one before it
+    pub fn increases(&self) -> Vec<usize> {
+        (1..self.depths.len())
+            .filter(|&i| self.depths[i] > self.depths[i - 1])
+            .collect()
+    }
+
+    /// The first of the longest stretches where every depth is deeper than the last
+    pub fn longest_increasing_run(&self) -> Option<Run> {
+        self.runs(|a, b| b > a)
+            .max_by_key(|r| (r.len(), Reverse(r.start)))
+    }
+
+    /// Every stretch of two or more depths where the depth stays the same
+    pub fn plateaus(&self) -> Vec<Run> {
+        self.runs(|a, b| b == a).filter(|r| r.len() > 1).collect()
+    }
+
+    /// Split the series into maximal runs where every neighbouring pair passes `continues`
+    fn runs<F>(&self, continues: F) -> impl Iterator<Item = Run> + '_
+    where
+        F: Fn(u32, u32) -> bool + 'static,
+    {
+        let mut start = 0;
+
+        (1..=self.depths.len()).filter_map(move |i| {
+            if i < self.depths.len() && continues(self.depths[i - 1], self.depths[i]) {
+                return None;
+            }
+
+            let run = Run { start, end: i - 1 };
+            start = i;
+            Some(run)
+        })
+    }
+
+    /// The biggest change between two neighbouring depths, up or down. Ties go to the first.
+    pub fn largest_jump(&self) -> Option<Jump> {
+        (1..self.depths.len())
+            .map(|i| Jump {
+                index: i,
+                from: self.depths[i - 1],
+                to: self.depths[i],
+            })
+            .max_by_key(|j| (j.from.abs_diff(j.to), Reverse(j.index)))
+    }
+
+    /// The sum of every full window of `window` depths, in order
+    pub fn window_sums(&self, window: usize) -> Vec<u64> {
+        if window == 0 {
+            return Vec::new();
+        }
+
+        self.depths
+            .windows(window)
+            .map(|w| w.iter().copied().map(u64::from).sum())
+            .collect()
+    }
+
+    /// The profile as CSV, with the sum of the window ending at each depth once there's a full
+    /// window of them
+    pub fn to_csv(&self, window: usize) -> String {
+        let sums = self.window_sums(window);
+        let mut csv = String::from("index,depth,window_sum\n");
+
+        for (i, depth) in self.depths.iter().enumerate() {
+            let sum = (i + 1)
+                .checked_sub(window)
+                .and_then(|w| sums.get(w))
+                .map(u64::to_string)
+                .unwrap_or_default();
+            writeln!(csv, "{i},{depth},{sum}").unwrap();
+        }
+
+        csv
+    }
+}
+
+pub struct SonarSweep;
+
+impl Solution for SonarSweep {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Sonar Sweep"
+    }
+
+    fn name(&self) -> &'static str {
+        "sonar_sweep"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        sweep_increases(input.lines()).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        sweep_window_increases(input.lines()).map(Into::into)
+    }
+
+    fn solve_reader(&self, part: u8, reader: &mut dyn BufRead) -> Result<Answer> {
+        match part {
+            1 => sweep_increases_from_reader(reader).map(Into::into),
+            2 => sweep_window_increases_from_reader(reader).map(Into::into),
+            _ => Err(Error::NoSuchPart { day: DAY, part }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"199
+200
+208
+210
+200
+207
+240
+269
+260
+263"#;
+
+    #[test]
+    fn it_calculates_sweep_increases() {
+        assert_eq!(Ok(7), sweep_increases(DATA.lines()));
+    }
+
+    #[test]
+    fn it_calculates_sweep_window_increases() {
+        assert_eq!(Ok(5), sweep_window_increases(DATA.lines()));
+    }
+
+    #[test]
+    fn it_calculates_increases_for_any_window() {
+        let depths: Vec<u32> = DATA.lines().map(|l| l.parse().unwrap()).collect();
+
+        for window in 1..=12 {
+            let sums: Vec<u32> = depths.windows(window).map(|w| w.iter().sum()).collect();
+            let expected = sums.windows(2).filter(|w| w[1] > w[0]).count();
+
+            assert_eq!(
+                Ok(expected),
+                sweep_window_increases_n(DATA.lines(), window),
+                "window {window}"
+            );
+        }
+        assert_eq!(
+            Ok(5),
+            sweep_window_increases_n_from_reader(DATA.as_bytes(), 5)
+        );
+    }
+
+    #[test]
+    fn it_needs_a_window() {
+        assert!(sweep_window_increases_n(DATA.lines(), 0).is_err());
+    }
+
+    #[test]
+    fn it_reads_depths_from_a_reader() {
+        assert_eq!(Ok(7), sweep_increases_from_reader(DATA.as_bytes()));
+        assert_eq!(Ok(5), sweep_window_increases_from_reader(DATA.as_bytes()));
+    }
+
+    #[test]
+    fn it_skips_corrupted_depths_when_lenient() {
+        let log = "199\n200\n12a4\n210\n\n207\n2";
+
+        assert_eq!(
+            Ok(LenientSweep {
+                increases: 2,
+                skipped: vec![Error::parse(DAY, 3, 1, "12a4"), Error::blank(DAY, 5)]
+            }),
+            sweep_window_increases_lenient(log.lines(), 1)
+        );
+        assert_eq!(
+            Ok(LenientSweep {
+                increases: 5,
+                skipped: vec![]
+            }),
+            sweep_window_increases_lenient_from_reader(DATA.as_bytes(), 3)
+        );
+    }
+
+    #[test]
+    fn it_analyses_the_depth_profile() {
+        let profile = DepthProfile::parse(DATA.lines()).unwrap();
+
+        assert_eq!(vec![1, 2, 3, 5, 6, 7, 9], profile.increases());
+        assert_eq!(
+            Some(Run { start: 0, end: 3 }),
+            profile.longest_increasing_run()
+        );
+        assert_eq!(
+            Some(Jump {
+                index: 6,
+                from: 207,
+                to: 240
+            }),
+            profile.largest_jump()
+        );
+        assert!(profile.plateaus().is_empty());
+        assert_eq!(
+            vec![607, 618, 618, 617, 647, 716, 769, 792],
+            profile.window_sums(3)
+        );
+    }
+
+    #[test]
+    fn it_finds_plateaus() {
+        let profile = DepthProfile::parse(["1", "1", "1", "2", "2", "3"].into_iter()).unwrap();
+
+        assert_eq!(
+            vec![Run { start: 0, end: 2 }, Run { start: 3, end: 4 }],
+            profile.plateaus()
+        );
+        assert_eq!(
+            Some(Run { start: 2, end: 3 }),
+            profile.longest_increasing_run()
+        );
+        assert_eq!(
+            None,
+            DepthProfile::parse("".lines()).unwrap().largest_jump()
+        );
+    }
+
+    #[test]
+    fn it_exports_the_profile_as_csv() {
+        let profile = DepthProfile::from_reader("199\n200\n208\n210".as_bytes()).unwrap();
+
+        assert_eq!(
+            "index,depth,window_sum\n0,199,\n1,200,\n2,208,607\n3,210,618\n",
+            profile.to_csv(3)
+        );
+    }
+
+    #[test]
+    fn it_reports_corrupted_depths() {
+        assert_eq!(
+            Err(Error::parse(DAY, 2, 1, "12a4")),
+            sweep_increases(["199", "12a4", "208"].into_iter())
+        );
+        assert_eq!(
+            "Day 1: line 2 is blank",
+            sweep_increases(["1", "", "3"].into_iter())
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use nom::character::complete::{char, digit1};
+use nom::combinator::map_res;
+use nom::multi::separated_list1;
+
+use crate::error::{Error, Result};
+use crate::input::{self, normalize};
+use crate::solution::{prepared, Answer, Prepared, Solution};
+use crate::utils::read_to_string;
+
+const DAY: u8 = 7;
+
+fn sum_numbers_until(num: u32) -> u64 {
+    let num = u64::from(num);
+    // Even the largest u32 can't overflow here, as num * (num + 1) stays below 2^64
+    num * (num + 1) / 2
+}
+
+fn parse_crabs(data: &str) -> Result<Vec<u32>> {
+    let data = &*normalize(data);
+    let (rest, crab_positions) = separated_list1(char(','), map_res(digit1, str::parse))(data)
+        .map_err(|e| Error::nom(DAY, data, e))?;
+    input::finish(DAY, data, rest)?;
+
+    Ok(crab_positions)
+}
+
+/// Find the least fuel needed to line up every crab, given the fuel one crab burns to move a distance
+fn crab_alignment<F>(crab_positions: Vec<u32>, fuel_calc: F) -> Result<u64>
+where
+    F: Fn(u32) -> u64,
+{
+    let crab_count = crab_positions.len();
+
+    let mapped_crab_positions: HashMap<u32, u32> =
+        crab_positions
+            .into_iter()
+            .fold(HashMap::with_capacity(crab_count), |mut hm, pos| {
+                let init = 1;
+                hm.entry(pos).and_modify(|c| *c += init).or_insert(init);
+                hm
+            });
+
+    let possible_alignments = mapped_crab_positions.keys().min().copied().unwrap_or(0)
+        ..=mapped_crab_positions.keys().max().copied().unwrap_or(0);
+
+    // A position needing more fuel than 64 bits can count can't be the cheapest, so it's only an
+    // error when every position does
+    possible_alignments
+        .filter_map(|pos| {
+            mapped_crab_positions
+                .iter()
+                .try_fold(0u64, |total, (p, c)| {
+                    fuel_calc(p.abs_diff(pos))
+                        .checked_mul(u64::from(*c))
+                        .and_then(|fuel| total.checked_add(fuel))
+                })
+        })
+        .min()
+        .ok_or_else(|| Error::calc(DAY, "The fuel needed doesn't fit in 64 bits"))
+}
+
+fn constant_alignment(crab_positions: Vec<u32>) -> Result<u64> {
+    crab_alignment(crab_positions, u64::from)
+}
+
+fn increasing_alignment(crab_positions: Vec<u32>) -> Result<u64> {
+    crab_alignment(crab_positions, sum_numbers_until)
+}
+
+pub fn crab_alignment_constant(data: &str) -> Result<u64> {
+    constant_alignment(parse_crabs(data)?)
+}
+
+pub fn crab_alignment_increasing(data: &str) -> Result<u64> {
+    increasing_alignment(parse_crabs(data)?)
+}
+
+pub fn crab_alignment_constant_from_reader<R: BufRead>(reader: R) -> Result<u64> {
+    crab_alignment_constant(&read_to_string(reader)?)
+}
+
+pub fn crab_alignment_increasing_from_reader<R: BufRead>(reader: R) -> Result<u64> {
+    crab_alignment_increasing(&read_to_string(reader)?)
+}
+
+pub struct TheTreacheryOfWhales;
+
+impl Solution for TheTreacheryOfWhales {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "The Treachery of Whales"
+    }
+
+    fn name(&self) -> &'static str {
+        "the_treachery_of_whales"
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        crab_alignment_constant(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        crab_alignment_increasing(input).map(Into::into)
+    }
+
+    fn prepare(&self, input: &str) -> Option<Result<Prepared>> {
+        Some(parse_crabs(input).map(|crabs| {
+            prepared(
+                DAY,
+                crabs,
+                |c| constant_alignment(c).map(Into::into),
+                |c| increasing_alignment(c).map(Into::into),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_crab_alignment_constant() {
+        if let Ok(alignment) = crab_alignment_constant(DATA) {
+            assert_eq!(37, alignment);
+        }
+    }
+
+    #[test]
+    fn test_crab_alignment_increasing() {
+        if let Ok(alignment) = crab_alignment_increasing(DATA) {
+            assert_eq!(168, alignment);
+        }
+    }
+
+    #[test]
+    fn it_counts_fuel_past_32_bits() {
+        assert_eq!(
+            Ok(13_333_466_667),
+            crab_alignment_increasing("0,200000,200000")
+        );
+        assert_eq!(
+            Err(Error::calc(DAY, "The fuel needed doesn't fit in 64 bits")),
+            crab_alignment(vec![0, 1], |_| u64::MAX)
+        );
+    }
+}
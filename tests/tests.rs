@@ -1,96 +1,108 @@
-use musical_waffle::*;
-
-#[test]
-fn day_one() {
-    let data = include_str!("../data/sonar_sweep.txt");
-
-    println!("Day 1: Sonar Sweep Part 1");
-    assert_eq!(1390, sonar_sweep::sweep_increases(data.lines()));
-    println!("Day 1: Sonar Sweep Part 2");
-    assert_eq!(1457, sonar_sweep::sweep_window_increases(data.lines()));
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use musical_waffle::aoc::has_input;
+use musical_waffle::registry::YEARS;
+use musical_waffle::verify::{verify, Answers, Check, Status};
+use musical_waffle::Solution;
+
+fn data_dir(year: u16) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join(year.to_string())
 }
 
-#[test]
-fn day_two() {
-    let data = include_str!("../data/dive.txt");
-
-    println!("Day 2: Dive! Part 1");
-    assert_eq!(1_484_118, dive::plot_course(data.lines()));
-    println!("Day 2: Dive! Part 2");
-    assert_eq!(1_463_827_010, dive::plot_aimed_course(data.lines()));
+fn input_path(dir: &Path, solution: &dyn Solution) -> PathBuf {
+    dir.join(format!("{}.txt", solution.name()))
 }
 
-#[test]
-fn day_three() {
-    let data = include_str!("../data/binary_diagnostic.txt");
+/// Read a day's input from `data/<year>`, panicking with the path if there's no file to read
+fn read_input(year: u16, solution: &dyn Solution) -> String {
+    let path = input_path(&data_dir(year), solution);
 
-    println!("Day 3: Binary Diagnostic Part 1");
-    assert_eq!(
-        3_309_596,
-        binary_diagnostic::diagnose_power_consumption(data.lines())
-    );
-    println!("Day 3: Binary Diagnostic Part 2");
-    assert_eq!(
-        2_981_085,
-        binary_diagnostic::diagnose_life_support(data.lines())
-    );
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Couldn't read {}: {e}", path.display()))
 }
 
-#[test]
-fn day_four() {
-    let data = include_str!("../data/giant_squid.txt");
-
-    println!("Day 4: Giant Squid Part 1");
-    assert_eq!(Some(27027), giant_squid::first_winning_board_score(data));
-    println!("Day 4: Giant Squid Part 2");
-    assert_eq!(Some(36975), giant_squid::last_winning_board_score(data));
+/// Check every day in `dir`, requiring a pass for each one with a real input there
+fn assert_all_pass(year: u16, days: &[&dyn Solution], dir: &Path) {
+    let answers = Answers::load(&dir.join("answers.txt")).expect("Couldn't load answers");
+
+    let checks = verify(days, dir, &answers);
+    for check in &checks {
+        println!("{year} {check}");
+    }
+
+    let failures: Vec<&Check> = checks
+        .iter()
+        .filter(|c| {
+            let solution = days.iter().find(|s| s.day() == c.day).unwrap();
+            let path = input_path(dir, *solution);
+            assert!(path.exists(), "{} is missing", path.display());
+
+            has_input(&path) && !matches!(c.status, Status::Pass(_))
+        })
+        .collect();
+    assert!(failures.is_empty(), "{year}: {failures:#?}");
 }
 
 #[test]
-fn day_five() {
-    let data = include_str!("../data/hydrothermal_venture.txt");
-
-    println!("Day 5: Hydrothermal Venture Part 1");
-    assert_eq!(
-        Ok(7085),
-        hydrothermal_venture::right_angle_dangerous_points(data)
-    );
-    println!("Day 5: Hydrothermal Venture Part 2");
-    assert_eq!(Ok(20271), hydrothermal_venture::all_dangerous_points(data));
+fn every_day_matches_its_answer() {
+    for (year, days) in YEARS {
+        assert_all_pass(*year, days, &data_dir(*year));
+    }
 }
 
 #[test]
-fn day_six() {
-    let data = include_str!("../data/lanternfish.txt");
-
-    println!("Day 6: Lanternfish Part 1");
-    assert_eq!(Ok(360_268), lanternfish::simulate_fish(data, 80));
-    println!("Day 6: Lanternfish Part 2");
-    assert_eq!(Ok(1_632_146_183_902), lanternfish::simulate_fish(data, 256));
+fn every_day_solves_its_examples() {
+    for (year, days) in YEARS {
+        assert_all_pass(*year, days, &data_dir(*year).join("examples"));
+    }
 }
 
 #[test]
-fn day_seven() {
-    let data = include_str!("../data/the_treachery_of_whales.txt");
-
-    println!("Day 7: The Treachery of Whales Part 1");
-    assert_eq!(
-        Ok(355_989),
-        the_treachery_of_whales::crab_alignment_constant(data)
-    );
-    println!("Day 7: The Treachery of Whales Part 2");
-    assert_eq!(
-        Ok(102_245_489),
-        the_treachery_of_whales::crab_alignment_increasing(data)
-    );
+fn every_day_gives_the_same_answer_from_a_reader() {
+    for (year, days) in YEARS {
+        for solution in *days {
+            let input = read_input(*year, *solution);
+
+            for part in [1, 2] {
+                let path = input_path(&data_dir(*year), *solution);
+                let mut reader = BufReader::new(File::open(&path).unwrap());
+                assert_eq!(
+                    solution.solve(part, &input),
+                    solution.solve_reader(part, &mut reader),
+                    "{year} day {} part {part}",
+                    solution.day()
+                );
+            }
+        }
+    }
 }
 
 #[test]
-fn day_eight() {
-    let data = include_str!("../data/seven_segment_search.txt");
-
-    println!("Day 8: Seven Segment Search Part 1");
-    assert_eq!(521, seven_segment_search::unique_segment_total(data));
-    println!("Day 8: Seven Segment Search Part 2");
-    assert_eq!(1_016_804, seven_segment_search::solve_segments(data));
+fn every_day_accepts_input_saved_on_windows() {
+    for (year, days) in YEARS {
+        for solution in *days {
+            let input = read_input(*year, *solution);
+            let windows = format!("\u{feff}{}\r\n\r\n", input.replace('\n', "\r\n"));
+
+            for part in [1, 2] {
+                let expected = solution.solve(part, &input);
+                assert_eq!(
+                    expected,
+                    solution.solve(part, &windows),
+                    "{year} day {} part {part}",
+                    solution.day()
+                );
+                assert_eq!(
+                    expected,
+                    solution.solve_reader(part, &mut windows.as_bytes()),
+                    "{year} day {} part {part} from a reader",
+                    solution.day()
+                );
+            }
+        }
+    }
 }